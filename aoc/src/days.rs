@@ -0,0 +1,187 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// How an input can be split into independent records, removed one by one when minimizing
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Records {
+    #[default]
+    Lines,
+    /// Groups of lines separated by a blank line
+    Blocks,
+}
+
+impl Records {
+    const fn separator(self) -> &'static str {
+        match self {
+            Self::Lines => "\n",
+            Self::Blocks => "\n\n",
+        }
+    }
+
+    pub fn split(self, input: &str) -> Vec<&str> {
+        input.split(self.separator()).collect()
+    }
+
+    pub fn join(self, records: &[&str]) -> String {
+        records.join(self.separator())
+    }
+}
+
+/// A solution, registered by the `[package.metadata.aoc]` table of its manifest:
+///
+/// ```toml
+/// [package.metadata.aoc]
+/// year = 2021
+/// day = 4
+/// records = "blocks" # `lines` by default
+/// repl = true        # `false` by default
+/// ```
+#[derive(Debug)]
+pub struct Day {
+    pub package: String,
+    pub binary: String,
+    pub year: u16,
+    /// Day of the year, from 1 to 25
+    pub number: u8,
+    /// Directory of the package, holding its default `input.txt`
+    pub dir: PathBuf,
+    pub records: Records,
+    /// Whether the solution explores its parsed input through `aoc repl`
+    pub repl: bool,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+    targets: Vec<Target>,
+    metadata: Option<PackageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PackageMetadata {
+    aoc: Option<Registration>,
+}
+
+#[derive(Deserialize)]
+struct Registration {
+    year: u16,
+    day: u8,
+    #[serde(default)]
+    records: Records,
+    #[serde(default)]
+    repl: bool,
+}
+
+impl Package {
+    fn into_day(self) -> Option<Result<Day, String>> {
+        let registration = self.metadata?.aoc?;
+        let Some(binary) = self
+            .targets
+            .into_iter()
+            .find(|target| target.kind.iter().any(|kind| kind == "bin"))
+        else {
+            return Some(Err(format!("{} has no binary", self.name)));
+        };
+        Some(Ok(Day {
+            package: self.name,
+            binary: binary.name,
+            year: registration.year,
+            number: registration.day,
+            dir: self.manifest_path.parent()?.to_path_buf(),
+            records: registration.records,
+            repl: registration.repl,
+        }))
+    }
+}
+
+/// Lists the registered workspace members with `cargo metadata`
+fn discover() -> Result<Vec<Day>, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .current_dir(crate::workspace_root())
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .output()
+        .map_err(|e| format!("Failed to run cargo metadata: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid cargo metadata: {}", e))?;
+    let mut days = metadata
+        .packages
+        .into_iter()
+        .filter_map(Package::into_day)
+        .collect::<Result<Vec<Day>, String>>()?;
+    days.sort_by(|a, b| (a.year, a.number, &a.package).cmp(&(b.year, b.number, &b.package)));
+    Ok(days)
+}
+
+/// Every registered solution, ordered by year and day
+pub fn registry() -> Result<&'static [Day], String> {
+    static REGISTRY: OnceLock<Result<Vec<Day>, String>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(discover)
+        .as_deref()
+        .map_err(Clone::clone)
+}
+
+/// Days are numbered from 1 to 25, larger numbers are years
+pub const MAX_DAY: u16 = 25;
+
+/// Finds the days matching `arg`: a day of any year (`8`, `08`), a whole year (`2021`), a day of
+/// a year (`2021/8`) or a package name
+pub fn select(arg: &str) -> Result<Vec<&'static Day>, String> {
+    let matches: Box<dyn Fn(&Day) -> bool> = match arg.split_once('/') {
+        Some((year, day)) => {
+            let year: u16 = year
+                .parse()
+                .map_err(|_| format!("Invalid year `{}`", year))?;
+            let day: u8 = day.parse().map_err(|_| format!("Invalid day `{}`", day))?;
+            Box::new(move |d| d.year == year && d.number == day)
+        }
+        None => match arg.parse::<u16>() {
+            Ok(year) if year > MAX_DAY => Box::new(move |d| d.year == year),
+            Ok(day) => Box::new(move |d| u16::from(d.number) == day),
+            Err(_) => Box::new(|d| d.package == arg),
+        },
+    };
+    let selected: Vec<&'static Day> = registry()?.iter().filter(|day| matches(day)).collect();
+    if selected.is_empty() {
+        Err(format!("Unknown day `{}`", arg))
+    } else {
+        Ok(selected)
+    }
+}
+
+/// Finds the single day matching `arg`
+pub fn select_one(arg: &str) -> Result<&'static Day, String> {
+    match select(arg)?.as_slice() {
+        [day] => Ok(day),
+        days => Err(format!(
+            "`{}` matches several days ({}), use a package name",
+            arg,
+            days.iter()
+                .map(|day| day.package.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
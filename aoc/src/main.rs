@@ -0,0 +1,252 @@
+mod days;
+mod minimize;
+mod scaffold;
+mod supervisor;
+
+use aoc_common::explain::{self, Event};
+use aoc_common::{input, repl};
+use days::Day;
+use minimize::MinimizeArgs;
+use scaffold::NewArgs;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+use supervisor::{Limits, Outcome};
+
+const USAGE: &str = "\
+Usage:
+    aoc run [DAY]... [--explain] [LIMITS]
+        Builds and runs the selected days (all of them by default), each in a supervised child
+        process. A day exceeding the wall-clock timeout or the memory cap is reported instead of
+        hanging. With `--explain` the solutions narrate what they do.
+    aoc minimize DAY FILE --predicate panic|mismatch [--reference DAY] [--output FILE] [LIMITS]
+        Removes records from FILE as long as DAY still panics on it, or still disagrees with the
+        `--reference` solution, and writes the smallest failing input (`FILE.min` by default).
+    aoc repl DAY [FILE]
+        Loads the input of DAY (or FILE) and explores it interactively with day specific commands
+        (days with `repl = true` in their registration).
+    aoc new DAY --name TITLE [--year YEAR]
+        Creates the crate of a new solution (of 2021 by default) from a template, with an empty
+        `test.txt`, and registers it in the workspace, the runner and the README table.
+
+Days:
+    A DAY is a day number of any year (`8`), a whole year (`2021`), a day of a year (`2021/8`) or
+    a package name (`day_08_alternative`). Solutions register through the `[package.metadata.aoc]`
+    table of their manifest.
+
+Limits:
+    --timeout SECONDS    Wall-clock limit of every solution run
+    --memory MEGABYTES   Memory cap of every solution run, `0` to disable (unix only)";
+const DEFAULT_LIMITS: Limits = Limits {
+    timeout: Duration::from_mins(2),
+    memory: Some(4096 << 20),
+};
+
+fn flag_value<T>(flag: &str, value: Option<String>) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = value.ok_or_else(|| format!("Missing value for `{}`", flag))?;
+    value
+        .parse()
+        .map_err(|e| format!("Invalid value `{}` for `{}`: {}", value, flag, e))
+}
+
+/// Applies `flag` to `limits`, returns `false` if it isn't a limit flag
+fn parse_limit(
+    limits: &mut Limits,
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<bool, String> {
+    match flag {
+        "--timeout" => limits.timeout = Duration::from_secs(flag_value(flag, args.next())?),
+        "--memory" => {
+            let megabytes: u64 = flag_value(flag, args.next())?;
+            limits.memory = (megabytes > 0).then(|| megabytes.saturating_mul(1 << 20));
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+struct RunArgs {
+    days: Vec<&'static Day>,
+    explain: bool,
+    limits: Limits,
+}
+
+impl RunArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut days = vec![];
+        let mut explain = false;
+        let mut limits = DEFAULT_LIMITS;
+        while let Some(arg) = args.next() {
+            if arg == "--explain" {
+                explain = true;
+            } else if !parse_limit(&mut limits, &arg, &mut args)? {
+                days.extend(days::select(&arg)?);
+            }
+        }
+        if days.is_empty() {
+            days = days::registry()?.iter().collect();
+        }
+        Ok(Self {
+            days,
+            explain,
+            limits,
+        })
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner is a workspace member")
+}
+
+/// Builds `days` with the same profile as the runner, which puts their binaries next to it
+fn build(days: &[&Day]) -> Result<(), String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(workspace_root())
+        .args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    for day in days {
+        command.args(["-p", day.package.as_str()]);
+    }
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to run cargo: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Build failed: {}", status))
+    }
+}
+
+fn binary_path(day: &Day) -> Result<PathBuf, String> {
+    let runner = std::env::current_exe().map_err(|e| format!("Can't locate the runner: {}", e))?;
+    Ok(runner.with_file_name(format!("{}{}", day.binary, std::env::consts::EXE_SUFFIX)))
+}
+
+/// Command running the solution of `day` in `dir`, which must contain its `input.txt`
+fn day_command(day: &Day, dir: &Path) -> Result<Command, String> {
+    let mut command = Command::new(binary_path(day)?);
+    command.current_dir(dir);
+    Ok(command)
+}
+
+fn supervise_day(day: &Day, command: Command, limits: Limits) -> Result<Outcome, String> {
+    supervisor::supervise(command, limits)
+        .map_err(|e| format!("Failed to start {}: {}", day.package, e))
+}
+
+/// Prints the output of a solution, rendering its explain events
+fn print_output(stdout: &str) {
+    for line in stdout.lines() {
+        match Event::parse(line) {
+            Some(event) => println!("  > {}", event),
+            None => println!("{}", line),
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> Result<bool, String> {
+    build(&args.days)?;
+    let mut all_succeeded = true;
+    for day in &args.days {
+        let mut command = day_command(day, &day.dir)?;
+        if args.explain {
+            command.env(explain::ENV_VAR, "1");
+        }
+        let outcome = supervise_day(day, command, args.limits)?;
+        println!("== {} ==", day.package);
+        match outcome {
+            Outcome::Success { stdout, elapsed } => {
+                print_output(&stdout);
+                println!("Done in {:.2?}", elapsed);
+            }
+            Outcome::Failed { status, stderr } => {
+                all_succeeded = false;
+                print!("{}", stderr);
+                println!("Failed: {}", status);
+            }
+            Outcome::TimedOut => {
+                all_succeeded = false;
+                println!("Timed out after {:?}", args.limits.timeout);
+            }
+            Outcome::OutOfMemory => {
+                all_succeeded = false;
+                match args.limits.memory {
+                    Some(bytes) => println!("OOM: exceeded {} MiB", bytes >> 20),
+                    None => println!("OOM: killed by the system"),
+                }
+            }
+        }
+    }
+    Ok(all_succeeded)
+}
+
+struct ReplArgs {
+    day: &'static Day,
+    file: Option<PathBuf>,
+}
+
+impl ReplArgs {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let args: Vec<String> = args.collect();
+        let (day, file) = match args.as_slice() {
+            [day] => (day, None),
+            [day, file] if file == input::STDIN => {
+                return Err(String::from("The REPL reads its commands from stdin"))
+            }
+            [day, file] => (day, Some(PathBuf::from(file))),
+            _ => return Err(String::from("Expected a DAY and an optional FILE")),
+        };
+        let day = days::select_one(day)?;
+        if !day.repl {
+            return Err(format!("{} has no REPL", day.package));
+        }
+        // The solution runs in its own directory
+        let file = file
+            .map(|file| std::path::absolute(&file))
+            .transpose()
+            .map_err(|e| format!("Invalid input path: {}", e))?;
+        Ok(Self { day, file })
+    }
+}
+
+fn run_repl(args: &ReplArgs) -> Result<bool, String> {
+    build(&[args.day])?;
+    let mut command = day_command(args.day, &args.day.dir)?;
+    command.env(repl::ENV_VAR, "1").args(&args.file);
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to start {}: {}", args.day.package, e))?;
+    Ok(status.success())
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => RunArgs::parse(args).and_then(|args| run(&args)),
+        Some("minimize") => MinimizeArgs::parse(args).and_then(|args| minimize::run(&args)),
+        Some("repl") => ReplArgs::parse(args).and_then(|args| run_repl(&args)),
+        Some("new") => NewArgs::parse(args).and_then(|args| scaffold::run(&args)),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(true) => (),
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::days::{self, Day};
+use crate::supervisor::{Limits, Outcome};
+use crate::{build, day_command, flag_value, parse_limit, supervise_day, DEFAULT_LIMITS};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Reduced inputs can make a solution loop forever, which doesn't count as a failure
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Copy, Clone)]
+pub enum Predicate {
+    /// The solution exits unsuccessfully
+    Panic,
+    /// The solution and the reference both succeed but print different answers
+    Mismatch(&'static Day),
+}
+
+pub struct MinimizeArgs {
+    day: &'static Day,
+    file: PathBuf,
+    predicate: Predicate,
+    output: PathBuf,
+    limits: Limits,
+}
+
+impl MinimizeArgs {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut positional = vec![];
+        let mut predicate: Option<String> = None;
+        let mut reference = None;
+        let mut output: Option<PathBuf> = None;
+        let mut limits = Limits {
+            timeout: DEFAULT_TIMEOUT,
+            ..DEFAULT_LIMITS
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--predicate" => predicate = Some(flag_value(&arg, args.next())?),
+                "--reference" => {
+                    let day: String = flag_value(&arg, args.next())?;
+                    reference = Some(days::select_one(&day)?);
+                }
+                "--output" => output = Some(flag_value(&arg, args.next())?),
+                _ if parse_limit(&mut limits, &arg, &mut args)? => (),
+                _ => positional.push(arg),
+            }
+        }
+        let [day, file]: [String; 2] = positional
+            .try_into()
+            .map_err(|_| String::from("Expected a DAY and a FILE"))?;
+        let day = days::select_one(&day)?;
+        let predicate = match (predicate.as_deref(), reference) {
+            (Some("panic"), None) => Predicate::Panic,
+            (Some("mismatch"), Some(reference)) => Predicate::Mismatch(reference),
+            (Some("panic"), Some(_)) => {
+                return Err(String::from(
+                    "`--reference` is only used by the mismatch predicate",
+                ))
+            }
+            (Some("mismatch"), None) => {
+                return Err(String::from(
+                    "The mismatch predicate requires a `--reference` day",
+                ))
+            }
+            _ => return Err(String::from("Expected `--predicate panic|mismatch`")),
+        };
+        let file = PathBuf::from(file);
+        let output = output.unwrap_or_else(|| {
+            let mut name = file.clone().into_os_string();
+            name.push(".min");
+            name.into()
+        });
+        Ok(Self {
+            day,
+            file,
+            predicate,
+            output,
+            limits,
+        })
+    }
+
+    /// Runs the solution on `input` and checks whether it fails the predicate
+    fn fails(&self, dir: &Path, input: &str) -> Result<bool, String> {
+        fs::write(dir.join("input.txt"), input)
+            .map_err(|e| format!("Failed to write candidate input: {}", e))?;
+        let outcome = supervise_day(self.day, day_command(self.day, dir)?, self.limits)?;
+        Ok(match self.predicate {
+            Predicate::Panic => matches!(outcome, Outcome::Failed { .. }),
+            Predicate::Mismatch(reference) => {
+                let expected = supervise_day(reference, day_command(reference, dir)?, self.limits)?;
+                match (outcome, expected) {
+                    (
+                        Outcome::Success { stdout, .. },
+                        Outcome::Success {
+                            stdout: expected, ..
+                        },
+                    ) => stdout != expected,
+                    _ => false,
+                }
+            }
+        })
+    }
+
+    fn minimize(&self, input: &str, dir: &Path) -> Result<String, String> {
+        let records = self.day.records;
+        let mut runs = 0;
+        let mut fails = |candidate: &[&str]| {
+            runs += 1;
+            self.fails(dir, &records.join(candidate))
+        };
+        let all = records.split(input);
+        if !fails(&all)? {
+            return Err(format!(
+                "{} does not fail the predicate",
+                self.file.display()
+            ));
+        }
+        let minimal = ddmin(all.clone(), &mut fails)?;
+        println!(
+            "Reduced {} records to {} in {} runs",
+            all.len(),
+            minimal.len(),
+            runs
+        );
+        Ok(records.join(&minimal))
+    }
+}
+
+/// Zeller's delta debugging: reduces `records` to a subset for which `fails` still holds and
+/// where removing any single record makes it pass
+fn ddmin<T: Copy, E>(
+    mut records: Vec<T>,
+    mut fails: impl FnMut(&[T]) -> Result<bool, E>,
+) -> Result<Vec<T>, E> {
+    let mut granularity = 2;
+    while records.len() >= 2 {
+        let chunks: Vec<&[T]> = records
+            .chunks(records.len().div_ceil(granularity))
+            .collect();
+        let mut reduced = None;
+        for chunk in &chunks {
+            if fails(chunk)? {
+                reduced = Some((chunk.to_vec(), 2));
+                break;
+            }
+        }
+        // With two chunks the complements are the chunks themselves
+        if reduced.is_none() && chunks.len() > 2 {
+            for i in 0..chunks.len() {
+                let complement: Vec<T> = chunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .flat_map(|(_, chunk)| chunk.iter().copied())
+                    .collect();
+                if fails(&complement)? {
+                    reduced = Some((complement, (granularity - 1).max(2)));
+                    break;
+                }
+            }
+        }
+        match reduced {
+            Some((subset, new_granularity)) => {
+                records = subset;
+                granularity = new_granularity;
+            }
+            None if granularity >= records.len() => break,
+            None => granularity = (granularity * 2).min(records.len()),
+        }
+    }
+    Ok(records)
+}
+
+pub fn run(args: &MinimizeArgs) -> Result<bool, String> {
+    let mut required = vec![args.day];
+    if let Predicate::Mismatch(reference) = args.predicate {
+        required.push(reference);
+    }
+    build(&required)?;
+    let input = fs::read_to_string(&args.file)
+        .map_err(|e| format!("Failed to read {}: {}", args.file.display(), e))?;
+    let dir = std::env::temp_dir().join(format!("aoc-minimize-{}", std::process::id()));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let minimal = args.minimize(&input, &dir);
+    // Leftovers in the temporary directory are harmless
+    let _ = fs::remove_dir_all(&dir);
+    fs::write(&args.output, minimal?)
+        .map_err(|e| format!("Failed to write {}: {}", args.output.display(), e))?;
+    println!("Minimal input written to {}", args.output.display());
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_ddmin_pair() {
+        let records: Vec<u32> = (0..100).collect();
+        let minimal = ddmin(records, |candidate| {
+            Ok::<_, Infallible>(candidate.contains(&13) && candidate.contains(&42))
+        });
+        assert_eq!(minimal, Ok(vec![13, 42]));
+    }
+
+    #[test]
+    fn test_ddmin_threshold() {
+        let records: Vec<u32> = (1..=10).collect();
+        let minimal = ddmin(records, |candidate| {
+            Ok::<_, Infallible>(candidate.iter().sum::<u32>() >= 19)
+        })
+        .unwrap();
+        let sum = minimal.iter().sum::<u32>();
+        assert!(sum >= 19);
+        // Removing any record makes the predicate pass
+        assert!(minimal.iter().all(|v| sum - v < 19));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::days::{self, MAX_DAY};
+use crate::{flag_value, workspace_root};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Year of the existing solutions, whose crates are at the workspace root
+const DEFAULT_YEAR: u16 = 2021;
+
+pub struct NewArgs {
+    year: u16,
+    number: u8,
+    name: String,
+}
+
+impl NewArgs {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut positional = vec![];
+        let mut name: Option<String> = None;
+        let mut year = DEFAULT_YEAR;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--name" => name = Some(flag_value(&arg, args.next())?),
+                "--year" => year = flag_value(&arg, args.next())?,
+                _ => positional.push(arg),
+            }
+        }
+        let [day]: [String; 1] = positional
+            .try_into()
+            .map_err(|_| String::from("Expected a DAY"))?;
+        let day: u8 = day
+            .parse()
+            .ok()
+            .filter(|day| (1..=MAX_DAY).contains(&u16::from(*day)))
+            .ok_or_else(|| format!("Invalid day `{}`, expected 1 to {}", day, MAX_DAY))?;
+        let name = name.ok_or_else(|| String::from("Expected a `--name`"))?;
+        Ok(Self {
+            year,
+            number: day,
+            name,
+        })
+    }
+
+    /// Package name, and its directory relative to the workspace root
+    fn package(&self) -> (String, PathBuf) {
+        let package = format!("day_{:02}", self.number);
+        if self.year == DEFAULT_YEAR {
+            (package.clone(), package.into())
+        } else {
+            (
+                format!("y{}_{}", self.year, package),
+                Path::new(&format!("y{}", self.year)).join(package),
+            )
+        }
+    }
+}
+
+fn manifest(package: &str, year: u16, day: u8) -> String {
+    format!(
+        "[package]
+name = \"{}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[package.metadata.aoc]
+year = {}
+day = {}
+
+[dependencies]
+aoc_common = {{ path = \"{}\" }}
+
+[features]
+# Embed `input.txt` in the binary, read when no input is given
+embedded = []
+",
+        package,
+        year,
+        day,
+        if year == DEFAULT_YEAR {
+            "../aoc_common"
+        } else {
+            "../../aoc_common"
+        }
+    )
+}
+
+const MAIN: &str = "const FILE_PATH: &str = \"input.txt\";
+
+fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+fn part_1(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+fn part_2(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+fn main() {
+    let input = aoc_common::input!(FILE_PATH).unwrap();
+    let lines = parse(&input);
+    println!(\"Part 1: {}\", part_1(&lines));
+    println!(\"Part 2: {}\", part_2(&lines));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let lines = parse(include_str!(\"../test.txt\"));
+        assert_eq!(part_1(&lines), 0);
+        assert_eq!(part_2(&lines), 0);
+    }
+}
+";
+
+/// Adds `member` to the `members` list of the workspace manifest, keeping it sorted
+fn add_member(manifest: &str, member: &str) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .map(|i| i + "members = [".len())
+        .ok_or_else(|| String::from("No workspace members in Cargo.toml"))?;
+    let end = start
+        + manifest[start..]
+            .find(']')
+            .ok_or_else(|| String::from("Unterminated workspace members in Cargo.toml"))?;
+    let mut members: Vec<&str> = manifest[start..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"'))
+        .filter(|member| !member.is_empty())
+        .collect();
+    if members.contains(&member) {
+        return Err(format!("{} is already a workspace member", member));
+    }
+    members.push(member);
+    members.sort_unstable();
+    let members = members
+        .iter()
+        .map(|member| format!("    \"{}\"", member))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    Ok(format!(
+        "{}\n{}\n{}",
+        &manifest[..start],
+        members,
+        &manifest[end..]
+    ))
+}
+
+/// Inserts the row of `day` in the README table, ordered by day
+fn add_readme_row(readme: &str, day: u8, name: &str, dir: &str) -> Result<String, String> {
+    let row = format!("| {:02} | [{}]({}/src/main.rs) | 0 / 2 |", day, name, dir);
+    let mut lines: Vec<&str> = readme.lines().collect();
+    let table_start = lines
+        .iter()
+        .position(|line| line.starts_with("| Day |"))
+        .ok_or_else(|| String::from("No day table in README.md"))?;
+    // Rows after the header and its separator line
+    let rows = table_start + 2;
+    let mut index = rows;
+    while let Some(line) = lines.get(index).filter(|line| line.starts_with('|')) {
+        let existing: Option<u8> = line
+            .trim_start_matches('|')
+            .split('|')
+            .next()
+            .and_then(|day| day.trim().parse().ok());
+        match existing {
+            Some(existing) if existing == day => {
+                return Err(format!("Day {} is already in README.md", day))
+            }
+            Some(existing) if existing > day => break,
+            _ => index += 1,
+        }
+    }
+    lines.insert(index, &row);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+pub fn run(args: &NewArgs) -> Result<bool, String> {
+    let (package, dir) = args.package();
+    if let Some(day) = days::registry()?
+        .iter()
+        .find(|day| day.year == args.year && day.number == args.number)
+    {
+        return Err(format!(
+            "{}/{} is already solved by {}",
+            args.year, args.number, day.package
+        ));
+    }
+    let root = workspace_root();
+    let path = root.join(&dir);
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    let member = dir.to_string_lossy().replace('\\', "/");
+    let workspace_manifest = root.join("Cargo.toml");
+    let workspace = add_member(&read(&workspace_manifest)?, &member)?;
+    let readme_path = root.join("README.md");
+    // The README table lists the solutions of the default year
+    let readme = if args.year == DEFAULT_YEAR {
+        Some(add_readme_row(
+            &read(&readme_path)?,
+            args.number,
+            &args.name,
+            &member,
+        )?)
+    } else {
+        None
+    };
+
+    fs::create_dir_all(path.join("src"))
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    write(
+        &path.join("Cargo.toml"),
+        &manifest(&package, args.year, args.number),
+    )?;
+    write(&path.join("src").join("main.rs"), MAIN)?;
+    write(&path.join("test.txt"), "")?;
+    write(&workspace_manifest, &workspace)?;
+    if let Some(readme) = readme {
+        write(&readme_path, &readme)?;
+    }
+    println!("Created {} in {}", package, path.display());
+    println!(
+        "Add the puzzle input to {}",
+        path.join("input.txt").display()
+    );
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_02\"\n]\n";
+        assert_eq!(
+            add_member(manifest, "day_01"),
+            Ok(String::from(
+                "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_01\",\n    \"day_02\"\n]\n"
+            ))
+        );
+        assert!(add_member(manifest, "day_02").is_err());
+    }
+
+    #[test]
+    fn test_add_readme_row() {
+        let readme = "# Title\n\n| Day | Name | Done |\n|-----|------|------|\n\
+                      | 01 | [A](day_01/src/main.rs) | 2 / 2 |\n\
+                      | 03 | [C](day_03/src/main.rs) | 2 / 2 |\n\nMore text\n";
+        assert_eq!(
+            add_readme_row(readme, 2, "B", "day_02"),
+            Ok(String::from(
+                "# Title\n\n| Day | Name | Done |\n|-----|------|------|\n\
+                 | 01 | [A](day_01/src/main.rs) | 2 / 2 |\n\
+                 | 02 | [B](day_02/src/main.rs) | 0 / 2 |\n\
+                 | 03 | [C](day_03/src/main.rs) | 2 / 2 |\n\nMore text\n"
+            ))
+        );
+        assert_eq!(
+            add_readme_row(readme, 4, "D", "day_04")
+                .map(|r| r.contains("| 03 | [C](day_03/src/main.rs) | 2 / 2 |\n| 04 | [D]")),
+            Ok(true)
+        );
+        assert!(add_readme_row(readme, 3, "C", "day_03").is_err());
+    }
+}
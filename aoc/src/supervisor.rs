@@ -0,0 +1,172 @@
+use std::io::{self, Read};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Message printed by the default allocation error handler before aborting
+const ALLOCATION_FAILURE: &str = "memory allocation of";
+/// Message printed by the dynamic loader when the libraries don't fit in the address space
+const MAPPING_FAILURE: &str = "failed to map segment";
+
+#[derive(Debug, Copy, Clone)]
+pub struct Limits {
+    pub timeout: Duration,
+    /// Address space limit in bytes, only enforced on unix targets
+    pub memory: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Success { stdout: String, elapsed: Duration },
+    Failed { status: ExitStatus, stderr: String },
+    TimedOut,
+    OutOfMemory,
+}
+
+#[cfg(unix)]
+#[allow(clippy::cast_possible_truncation)]
+fn apply_memory_limit(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // Safety: `setrlimit` is async-signal-safe and nothing is allocated between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &raw const limit) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_memory_limit(_command: &mut Command, _bytes: u64) {}
+
+/// Killed by the OOM killer, or crashed under a memory cap: a child running out of address space
+/// before its allocation error handler is set up, e.g. growing its stack, dies from a segfault
+#[cfg(unix)]
+fn killed_by_system(status: ExitStatus, capped: bool) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    match status.signal() {
+        Some(libc::SIGKILL) => true,
+        Some(libc::SIGSEGV) => capped,
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+const fn killed_by_system(_status: ExitStatus, _capped: bool) -> bool {
+    false
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buff = Vec::new();
+        if let Some(mut pipe) = pipe {
+            // A broken pipe only means the child is gone, whatever was read is kept
+            let _ = pipe.read_to_end(&mut buff);
+        }
+        String::from_utf8_lossy(&buff).into_owned()
+    })
+}
+
+/// Runs `command` to completion, killing it once `limits.timeout` is elapsed
+pub fn supervise(mut command: Command, limits: Limits) -> io::Result<Outcome> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(bytes) = limits.memory {
+        apply_memory_limit(&mut command, bytes);
+    }
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if start.elapsed() >= limits.timeout {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let elapsed = start.elapsed();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok(match status {
+        None => Outcome::TimedOut,
+        Some(status) if status.success() => Outcome::Success { stdout, elapsed },
+        Some(status)
+            if stderr.contains(ALLOCATION_FAILURE)
+                || stderr.contains(MAPPING_FAILURE)
+                || killed_by_system(status, limits.memory.is_some()) =>
+        {
+            Outcome::OutOfMemory
+        }
+        Some(status) => Outcome::Failed { status, stderr },
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    const LIMITS: Limits = Limits {
+        timeout: Duration::from_secs(5),
+        memory: None,
+    };
+
+    #[test]
+    fn test_success() {
+        let outcome = supervise(shell("echo 42"), LIMITS).unwrap();
+        assert!(matches!(outcome, Outcome::Success { stdout, .. } if stdout == "42\n"));
+    }
+
+    #[test]
+    fn test_failure() {
+        let outcome = supervise(shell("echo oops >&2; exit 3"), LIMITS).unwrap();
+        assert!(
+            matches!(outcome, Outcome::Failed { status, stderr } if status.code() == Some(3) && stderr == "oops\n")
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_millis(50),
+            memory: None,
+        };
+        let outcome = supervise(shell("exec sleep 5"), limits).unwrap();
+        assert!(matches!(outcome, Outcome::TimedOut));
+    }
+
+    #[test]
+    fn test_segfault() {
+        let segfault = "kill -SEGV $$";
+        let outcome = supervise(shell(segfault), LIMITS).unwrap();
+        assert!(matches!(outcome, Outcome::Failed { .. }));
+        let limits = Limits {
+            timeout: Duration::from_secs(5),
+            memory: Some(1 << 30),
+        };
+        let outcome = supervise(shell(segfault), limits).unwrap();
+        assert!(matches!(outcome, Outcome::OutOfMemory));
+    }
+}
@@ -0,0 +1,101 @@
+use core::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Integer types usable with the [`arith!`](crate::arith!) macro
+pub trait Arith: Copy + Display {
+    fn checked(self, op: Op, rhs: Self) -> Option<Self>;
+
+    #[must_use]
+    fn unchecked(self, op: Op, rhs: Self) -> Self;
+}
+
+macro_rules! impl_arith {
+    ($($int:ty),*) => {
+        $(
+            impl Arith for $int {
+                fn checked(self, op: Op, rhs: Self) -> Option<Self> {
+                    match op {
+                        Op::Add => self.checked_add(rhs),
+                        Op::Sub => self.checked_sub(rhs),
+                        Op::Mul => self.checked_mul(rhs),
+                    }
+                }
+
+                fn unchecked(self, op: Op, rhs: Self) -> Self {
+                    match op {
+                        Op::Add => self + rhs,
+                        Op::Sub => self - rhs,
+                        Op::Mul => self * rhs,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_arith!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Applies `op`, checking for overflows when `exprs` holds the source of both operands
+#[doc(hidden)]
+#[track_caller]
+pub fn apply<T: Arith>(lhs: T, op: Op, rhs: T, exprs: Option<(&str, &str)>) -> T {
+    match exprs {
+        Some((lhs_expr, rhs_expr)) => lhs.checked(op, rhs).unwrap_or_else(|| {
+            panic!(
+                "Arithmetic overflow in `{} {} {}` ({} {} {})",
+                lhs_expr, op, rhs_expr, lhs, op, rhs
+            )
+        }),
+        None => lhs.unchecked(op, rhs),
+    }
+}
+
+/// Computes `$lhs $op $rhs` (`arith!(Mul, a, b)` is `a * b`).
+///
+/// When the *calling* crate enables its `checked` feature the operation is checked, and an
+/// overflow panics with the offending expression instead of silently wrapping in release builds.
+#[macro_export]
+macro_rules! arith {
+    ($op:ident, $lhs:expr, $rhs:expr) => {
+        $crate::arith::apply(
+            $lhs,
+            $crate::arith::Op::$op,
+            $rhs,
+            cfg!(feature = "checked").then_some((stringify!($lhs), stringify!($rhs))),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(apply(6_u8, Op::Mul, 7, None), 42);
+        assert_eq!(apply(250_u8, Op::Add, 5, Some(("a", "b"))), 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "Arithmetic overflow in `a + b` (250 + 6)")]
+    fn test_checked_overflow() {
+        apply(250_u8, Op::Add, 6, Some(("a", "b")));
+    }
+}
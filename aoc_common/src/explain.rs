@@ -0,0 +1,139 @@
+//! Structured narration of what a solution does.
+//!
+//! Solutions write events with the [`explain!`](crate::explain!) macro. Events are only emitted,
+//! and their values only computed, when the `AOC_EXPLAIN` environment variable is set: disabled
+//! narration costs a single atomic load. Events are written to stdout, interleaved with the
+//! answers, and rendered as text by the runner.
+
+use std::fmt::{self, Display, Formatter};
+use std::io::Write;
+use std::sync::OnceLock;
+
+/// Environment variable enabling the events
+pub const ENV_VAR: &str = "AOC_EXPLAIN";
+/// Prefix of the stdout lines holding an event
+const MARKER: &str = "\u{1e}explain";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Event {
+    pub kind: String,
+    pub fields: Vec<(String, String)>,
+}
+
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var_os(ENV_VAR).is_some_and(|v| v != "0"))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => res.push('\t'),
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => res.push('\\'),
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+fn encode(kind: &str, fields: &[(&str, &dyn Display)]) -> String {
+    fields
+        .iter()
+        .fold(format!("{}\t{}", MARKER, kind), |line, (key, value)| {
+            format!("{}\t{}={}", line, key, escape(&value.to_string()))
+        })
+}
+
+/// Writes an event line, use the [`explain!`](crate::explain!) macro instead
+#[doc(hidden)]
+pub fn emit(kind: &str, fields: &[(&str, &dyn Display)]) {
+    // Narration is best effort and must never make a solution fail
+    let _ = writeln!(std::io::stdout().lock(), "{}", encode(kind, fields));
+}
+
+impl Event {
+    /// Parses an output line, `None` if it isn't an event
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.strip_prefix(MARKER)?.strip_prefix('\t')?.split('\t');
+        let kind = parts.next()?.to_string();
+        let fields = parts
+            .map(|field| {
+                let (key, value) = field.split_once('=')?;
+                Some((key.to_string(), unescape(value)))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { kind, fields })
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind.replace('_', " "))?;
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            let separator = if i == 0 { ':' } else { ',' };
+            write!(f, "{} {} {}", separator, key.replace('_', " "), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Narrates a step of a solution: `explain!("board_won", board = id, draw = number)`.
+///
+/// The values are only evaluated when narration is [enabled](crate::explain::enabled)
+#[macro_export]
+macro_rules! explain {
+    ($kind:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::explain::enabled() {
+            $crate::explain::emit(
+                $kind,
+                &[$((stringify!($key), &$value as &dyn ::core::fmt::Display)),*],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let line = encode("line_broken", &[("line", &3), ("found", &"a\tb\\c\n")]);
+        let event = Event::parse(&line).unwrap();
+        assert_eq!(event.kind, "line_broken");
+        assert_eq!(
+            event.fields,
+            vec![
+                ("line".to_string(), "3".to_string()),
+                ("found".to_string(), "a\tb\\c\n".to_string())
+            ]
+        );
+        assert_eq!(Event::parse("Part 1: 42"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let event = Event::parse(&encode("board_won", &[("board", &2), ("last_draw", &24)]));
+        assert_eq!(
+            event.unwrap().to_string(),
+            "board won: board 2, last draw 24"
+        );
+        let event = Event::parse(&encode("done", &[]));
+        assert_eq!(event.unwrap().to_string(), "done");
+    }
+}
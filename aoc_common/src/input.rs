@@ -0,0 +1,228 @@
+//! Where a solution reads its puzzle input from.
+//!
+//! Solutions call the [`input!`](crate::input!) macro, which reads the first command line
+//! argument: a file path, or `-` for stdin. Without argument the input is the one embedded in the
+//! binary when its `embedded` feature is enabled, and the default path otherwise, relative to the
+//! working directory or else to the directory of the solution's crate. Gzip compressed inputs are
+//! decompressed transparently.
+//!
+//! Solvers needing a single pass over very large inputs use [`reader!`](crate::reader!) instead,
+//! and parse the lines as they are read with [`records`].
+
+use flate2::read::GzDecoder;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Argument selecting stdin
+pub const STDIN: &str = "-";
+/// First bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Embedded(&'static str),
+}
+
+impl Source {
+    /// Source selected by `arg`, falling back to `embedded` then to `default_path`. A missing
+    /// `default_path` is looked up in `crate_dir`, so solutions also run from the workspace root
+    #[must_use]
+    pub fn new(
+        arg: Option<String>,
+        default_path: &str,
+        embedded: Option<&'static str>,
+        crate_dir: &str,
+    ) -> Self {
+        match (arg, embedded) {
+            (Some(arg), _) if arg == STDIN => Self::Stdin,
+            (Some(path), _) => Self::Path(path.into()),
+            (None, Some(input)) => Self::Embedded(input),
+            (None, None) if Path::new(default_path).exists() => Self::Path(default_path.into()),
+            (None, None) => Self::Path(Path::new(crate_dir).join(default_path)),
+        }
+    }
+
+    /// Reads the whole input
+    ///
+    /// # Errors
+    ///
+    /// Fails if the source can't be read or isn't valid (possibly gzip compressed) UTF-8
+    pub fn read(&self) -> Result<String, String> {
+        let bytes = match self {
+            Self::Path(path) => std::fs::read(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+            Self::Stdin => {
+                let mut bytes = vec![];
+                std::io::stdin()
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| format!("Failed to read stdin: {}", e))?;
+                bytes
+            }
+            Self::Embedded(input) => return Ok((*input).to_string()),
+        };
+        decode(bytes)
+    }
+
+    /// Opens the input to be read line by line, without loading it whole
+    ///
+    /// # Errors
+    ///
+    /// Fails if the source can't be opened
+    pub fn open(&self) -> Result<Box<dyn BufRead>, String> {
+        let reader: Box<dyn BufRead> = match self {
+            Self::Path(path) => {
+                Box::new(BufReader::new(std::fs::File::open(path).map_err(|e| {
+                    format!("Failed to open {}: {}", path.display(), e)
+                })?))
+            }
+            Self::Stdin => Box::new(std::io::stdin().lock()),
+            Self::Embedded(input) => Box::new(input.as_bytes()),
+        };
+        decode_stream(reader).map_err(|e| format!("Failed to read the input: {}", e))
+    }
+}
+
+fn decode(bytes: Vec<u8>) -> Result<String, String> {
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut input = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Invalid gzip input: {}", e))?;
+        Ok(input)
+    } else {
+        String::from_utf8(bytes).map_err(|e| format!("Invalid input: {}", e))
+    }
+}
+
+fn decode_stream(mut reader: Box<dyn BufRead>) -> std::io::Result<Box<dyn BufRead>> {
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(BufReader::new(GzDecoder::new(reader))))
+    } else {
+        Ok(reader)
+    }
+}
+
+/// Parses every line of `reader` as it is read, errors name the offending line
+pub fn records<T>(reader: impl BufRead) -> impl Iterator<Item = Result<T, String>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    reader.lines().enumerate().map(|(i, line)| {
+        line.map_err(|e| format!("Failed to read line {}: {}", i + 1, e))?
+            .parse()
+            .map_err(|e| format!("Invalid line {}: {}", i + 1, e))
+    })
+}
+
+/// Selects the input source of the solution, `$default_path` being used when no argument is given.
+///
+/// The *calling* crate's `embedded` feature embeds its `input.txt` in the binary, and its directory
+/// holds the default path when it isn't found in the working directory
+#[macro_export]
+macro_rules! source {
+    ($default_path:expr) => {{
+        #[cfg(feature = "embedded")]
+        let embedded = Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/input.txt"
+        )));
+        #[cfg(not(feature = "embedded"))]
+        let embedded = None;
+        $crate::input::Source::new(
+            std::env::args().nth(1),
+            $default_path,
+            embedded,
+            env!("CARGO_MANIFEST_DIR"),
+        )
+    }};
+}
+
+/// Reads the input of the solution, `$default_path` being used when no argument is given.
+///
+/// The *calling* crate's `embedded` feature embeds its `input.txt` in the binary
+#[macro_export]
+macro_rules! input {
+    ($default_path:expr) => {
+        $crate::source!($default_path).read()
+    };
+}
+
+/// Opens the input of the solution to stream its lines, like [`input!`](crate::input!)
+#[macro_export]
+macro_rules! reader {
+    ($default_path:expr) => {
+        $crate::source!($default_path).open()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn test_source() {
+        let embedded = Some("embedded");
+        assert_eq!(
+            Source::new(Some("-".to_string()), "input.txt", embedded, "day"),
+            Source::Stdin
+        );
+        assert_eq!(
+            Source::new(Some("a.txt".to_string()), "input.txt", embedded, "day"),
+            Source::Path("a.txt".into())
+        );
+        assert_eq!(
+            Source::new(None, "input.txt", embedded, "day"),
+            Source::Embedded("embedded")
+        );
+        // Tests run from the crate directory, which has no input
+        assert_eq!(
+            Source::new(None, "input.txt", None, "day"),
+            Source::Path("day/input.txt".into())
+        );
+        assert_eq!(
+            Source::new(None, "Cargo.toml", None, "day"),
+            Source::Path("Cargo.toml".into())
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"1\n2\n3\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(decode(compressed), Ok("1\n2\n3\n".to_string()));
+        assert_eq!(decode(b"plain".to_vec()), Ok("plain".to_string()));
+        assert!(decode(vec![0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_records() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"1\n2\nx\n").unwrap();
+        let compressed: &'static [u8] = encoder.finish().unwrap().leak();
+        let reader = decode_stream(Box::new(compressed)).unwrap();
+        assert_eq!(
+            records::<u32>(reader).collect::<Vec<_>>(),
+            vec![
+                Ok(1),
+                Ok(2),
+                Err(String::from(
+                    "Invalid line 3: invalid digit found in string"
+                ))
+            ]
+        );
+        let reader = Source::Embedded("4\n5").open().unwrap();
+        assert_eq!(
+            records(reader).collect::<Result<Vec<u32>, _>>(),
+            Ok(vec![4, 5])
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Helpers shared by the daily solutions
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod arith;
+#[cfg(feature = "std")]
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod input;
+#[cfg(feature = "repl")]
+pub mod repl;
+pub mod simulation;
+
+/// Without `std` there is nowhere to narrate to: events are discarded and their values never
+/// evaluated
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! explain {
+    ($kind:literal $(, $key:ident = $value:expr)* $(,)?) => {};
+}
@@ -0,0 +1,101 @@
+//! Interactive exploration of a parsed input.
+//!
+//! When the `AOC_REPL` environment variable is set (`aoc repl DAY` does it), a solution hands its
+//! parsed input to [`run`] instead of solving. Each line is split on whitespace and given to the
+//! day's [`Session`], except for the built-in `help`, `history` and `quit` commands.
+
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+/// Environment variable requesting a REPL session instead of the answers
+pub const ENV_VAR: &str = "AOC_REPL";
+
+/// Day specific commands
+pub trait Session {
+    /// Usage of the commands, one per line
+    fn help(&self) -> &'static str;
+
+    /// Runs `command`, returning the text to print
+    ///
+    /// # Errors
+    ///
+    /// Fails on an unknown command or invalid arguments, the session then goes on
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+#[must_use]
+pub fn requested() -> bool {
+    std::env::var_os(ENV_VAR).is_some_and(|v| v != "0")
+}
+
+/// Parses the argument at `index`, naming it `name` in errors
+///
+/// # Errors
+///
+/// Fails if the argument is missing or can't be parsed
+pub fn arg<T: std::str::FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let arg = args
+        .get(index)
+        .ok_or_else(|| format!("Missing argument {}", name))?;
+    arg.parse()
+        .map_err(|_| format!("Invalid {} `{}`", name, arg))
+}
+
+/// Reads and executes commands until `quit` or the end of input
+///
+/// # Errors
+///
+/// Fails if the terminal can't be read
+pub fn run(name: &str, session: &mut impl Session) -> Result<(), String> {
+    let mut editor = DefaultEditor::new().map_err(|e| format!("Failed to open a REPL: {}", e))?;
+    let mut history: Vec<String> = vec![];
+    println!("Exploring {}, `help` lists the commands", name);
+    loop {
+        let line = match editor.readline(&format!("{}> ", name)) {
+            Ok(line) => line,
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => return Ok(()),
+            Err(e) => return Err(format!("Failed to read a command: {}", e)),
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((command, args)) = words.split_first() else {
+            continue;
+        };
+        // The history is in-memory only, failing to record a line is harmless
+        let _ = editor.add_history_entry(line.as_str());
+        history.push(line.clone());
+        match *command {
+            "quit" | "exit" => return Ok(()),
+            "help" => println!(
+                "{}\nhistory\n    List the commands of this session\nquit",
+                session.help()
+            ),
+            "history" => history
+                .iter()
+                .enumerate()
+                .for_each(|(i, line)| println!("{:>4}  {}", i + 1, line)),
+            _ => match session.execute(command, args) {
+                Ok(output) => println!("{}", output),
+                Err(e) => println!("Error: {}", e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arg() {
+        let args = ["3", "x"];
+        assert_eq!(arg::<usize>(&args, 0, "STEPS"), Ok(3));
+        assert_eq!(
+            arg::<usize>(&args, 1, "STEPS"),
+            Err(String::from("Invalid STEPS `x`"))
+        );
+        assert_eq!(
+            arg::<usize>(&args, 2, "STEPS"),
+            Err(String::from("Missing argument STEPS"))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A repetition in the states of a [`Simulation`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    /// Number of steps before the first state of the cycle
+    pub start: usize,
+    /// Number of steps between two identical states
+    pub period: usize,
+}
+
+/// A state evolving one discrete step at a time
+pub trait Simulation {
+    /// Part of the simulation identifying a configuration: two equal states must evolve identically
+    type State: Hash + Eq;
+
+    /// Advances the simulation by one step
+    fn step(&mut self);
+
+    fn state(&self) -> Self::State;
+
+    fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Runs `steps` steps, calling `observer` with the step number (starting at 1) after each one
+    fn run_with(&mut self, steps: usize, mut observer: impl FnMut(usize, &Self))
+    where
+        Self: Sized,
+    {
+        for step in 1..=steps {
+            self.step();
+            observer(step, self);
+        }
+    }
+
+    /// Steps until `predicate` holds, returns the number of steps taken
+    fn run_until(&mut self, mut predicate: impl FnMut(&Self) -> bool) -> usize
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        while !predicate(self) {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Steps until a state is seen twice, giving up after `max_steps` steps.
+    /// The simulation is left on the first repeated state.
+    #[cfg(feature = "std")]
+    fn find_cycle(&mut self, max_steps: usize) -> Option<Cycle> {
+        let mut seen = HashMap::new();
+        for step in 0..=max_steps {
+            if let Some(start) = seen.insert(self.state(), step) {
+                return Some(Cycle {
+                    start,
+                    period: step - start,
+                });
+            }
+            if step < max_steps {
+                self.step();
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `x -> x² + 1 mod 20`: 0, 1, 2, 5, 6, 17, 10, 1, ...
+    struct Squares {
+        value: u32,
+        steps: usize,
+    }
+
+    impl Simulation for Squares {
+        type State = u32;
+
+        fn step(&mut self) {
+            self.value = (self.value * self.value + 1) % 20;
+            self.steps += 1;
+        }
+
+        fn state(&self) -> Self::State {
+            self.value
+        }
+    }
+
+    const fn squares() -> Squares {
+        Squares { value: 0, steps: 0 }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut sim = squares();
+        sim.run(3);
+        assert_eq!(sim.value, 5);
+        let mut values = vec![];
+        sim.run_with(2, |step, s| values.push((step, s.value)));
+        assert_eq!(values, vec![(1, 6), (2, 17)]);
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut sim = squares();
+        assert_eq!(sim.run_until(|s| s.value == 17), 5);
+        assert_eq!(sim.run_until(|s| s.value == 17), 0);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut sim = squares();
+        assert_eq!(sim.find_cycle(3), None);
+        let mut sim = squares();
+        assert_eq!(
+            sim.find_cycle(100),
+            Some(Cycle {
+                start: 1,
+                period: 6
+            })
+        );
+        assert_eq!(sim.steps, 7);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Change;
+use std::collections::VecDeque;
+
+/// Summary of a window of readings, windows being compared by their summaries
+pub trait Aggregate {
+    /// Aggregates the readings of a full window, oldest first
+    fn aggregate(&self, window: &[u32]) -> f64;
+}
+
+impl Aggregate for Box<dyn Aggregate> {
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        self.as_ref().aggregate(window)
+    }
+}
+
+pub struct Sum;
+
+impl Aggregate for Sum {
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        window.iter().map(|reading| f64::from(*reading)).sum()
+    }
+}
+
+pub struct Mean;
+
+impl Aggregate for Mean {
+    #[allow(clippy::cast_precision_loss)]
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        Sum.aggregate(window) / window.len() as f64
+    }
+}
+
+pub struct Median;
+
+impl Aggregate for Median {
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        let mut sorted = window.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            f64::midpoint(f64::from(sorted[middle - 1]), f64::from(sorted[middle]))
+        } else {
+            f64::from(sorted[middle])
+        }
+    }
+}
+
+pub struct Max;
+
+impl Aggregate for Max {
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        window.iter().max().copied().map_or(0., f64::from)
+    }
+}
+
+/// Exponentially weighted average, `alpha` being the weight of the newest reading
+pub struct Ewma {
+    pub alpha: f64,
+}
+
+impl Aggregate for Ewma {
+    fn aggregate(&self, window: &[u32]) -> f64 {
+        window
+            .iter()
+            .map(|reading| f64::from(*reading))
+            .reduce(|average, reading| self.alpha.mul_add(reading, (1. - self.alpha) * average))
+            .unwrap_or_default()
+    }
+}
+
+/// Parses `sum`, `mean`, `median`, `max` or `ewma:ALPHA`. The sum is `None`: comparing the
+/// endpoints of consecutive windows compares their sums, without aggregating them
+pub fn parse(name: &str) -> Result<Option<Box<dyn Aggregate>>, String> {
+    Ok(Some(match name.split_once(':') {
+        None if name == "sum" => return Ok(None),
+        None if name == "mean" => Box::new(Mean),
+        None if name == "median" => Box::new(Median),
+        None if name == "max" => Box::new(Max),
+        Some(("ewma", alpha)) => {
+            let alpha: f64 = alpha
+                .parse()
+                .ok()
+                .filter(|alpha| (0. ..=1.).contains(alpha))
+                .ok_or_else(|| format!("Invalid EWMA weight `{}`, expected 0 to 1", alpha))?;
+            Box::new(Ewma { alpha })
+        }
+        _ => {
+            return Err(format!(
+                "Unknown aggregate `{}`, expected sum, mean, median, max or ewma:ALPHA",
+                name
+            ))
+        }
+    }))
+}
+
+/// Sliding window of `size` readings comparing the aggregate of consecutive windows
+pub struct AggregateWindow<A> {
+    aggregate: A,
+    size: usize,
+    readings: VecDeque<u32>,
+    previous: Option<f64>,
+}
+
+impl<A: Aggregate> AggregateWindow<A> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0
+    pub fn new(size: usize, aggregate: A) -> Self {
+        assert!(size > 0, "Empty window");
+        Self {
+            aggregate,
+            size,
+            readings: VecDeque::with_capacity(size),
+            previous: None,
+        }
+    }
+
+    /// Adds a reading, returning how the window changed once a second window is full
+    pub fn push(&mut self, reading: u32) -> Option<Change> {
+        if self.readings.len() == self.size {
+            self.readings.pop_front();
+        }
+        self.readings.push_back(reading);
+        if self.readings.len() < self.size {
+            return None;
+        }
+        let value = self.aggregate.aggregate(self.readings.make_contiguous());
+        let change = self
+            .previous
+            .map(|previous| Change::of_f64(value - previous));
+        self.previous = Some(value);
+        change
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DepthWindows;
+
+    fn changes(aggregate: impl Aggregate) -> Vec<Option<Change>> {
+        let mut window = AggregateWindow::new(3, aggregate);
+        READINGS
+            .into_iter()
+            .map(|reading| window.push(reading))
+            .collect()
+    }
+
+    const READINGS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_sum_fast_path() {
+        let fast: Vec<_> = READINGS
+            .into_iter()
+            .depth_windows([3])
+            .map(|event| event.changes()[0])
+            .collect();
+        assert_eq!(changes(Sum), fast);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_aggregates() {
+        let window = [3, 1, 4, 1];
+        assert_eq!(Mean.aggregate(&window), 2.25);
+        assert_eq!(Median.aggregate(&window), 2.);
+        assert_eq!(Median.aggregate(&window[..3]), 3.);
+        assert_eq!(Max.aggregate(&window), 4.);
+        assert_eq!(Ewma { alpha: 0.5 }.aggregate(&window), 2.);
+        let increases = changes(Median)
+            .into_iter()
+            .filter(|change| *change == Some(Change::Increase))
+            .count();
+        assert_eq!(increases, 4);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Delay before reading again the end of a followed file
+pub const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Reader waiting at the end of its input for more data to be appended, like `tail -f`.
+///
+/// Reads never reach the end of the input, so a line being written is only parsed once its
+/// newline arrives
+pub struct Follow<R> {
+    inner: R,
+    poll_interval: Duration,
+}
+
+impl Follow<File> {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        Ok(Self::new(file, POLL_INTERVAL))
+    }
+}
+
+impl<R: Read> Follow<R> {
+    pub const fn new(inner: R, poll_interval: Duration) -> Self {
+        Self {
+            inner,
+            poll_interval,
+        }
+    }
+}
+
+impl<R: Read> Read for Follow<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.inner.read(buf)? {
+                0 if !buf.is_empty() => thread::sleep(self.poll_interval),
+                read => return Ok(read),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_increases, DepthWindows};
+    use aoc_common::input::records;
+    use std::fs::OpenOptions;
+    use std::io::{BufReader, Write};
+
+    #[test]
+    fn test_follow_appended_readings() {
+        let path = std::env::temp_dir().join(format!("day_01_follow_{}.txt", std::process::id()));
+        std::fs::write(&path, "199\n200\n2").unwrap();
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                let mut file = OpenOptions::new().append(true).open(path).unwrap();
+                // The partial `2` line is completed by a later write
+                for chunk in ["08\n210\n", "200\n207\n240\n26", "9\n260\n263\n"] {
+                    thread::sleep(Duration::from_millis(20));
+                    file.write_all(chunk.as_bytes()).unwrap();
+                }
+            })
+        };
+        let follow = Follow::new(File::open(&path).unwrap(), Duration::from_millis(5));
+        let readings = records(BufReader::new(follow)).map(Result::unwrap);
+        let running: Vec<[usize; 2]> = readings
+            .depth_windows([1, 3])
+            .take(10)
+            .scan([0; 2], |counts, event| {
+                count_increases(counts, &event);
+                Some(*counts)
+            })
+            .collect();
+        writer.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(running[2], [2, 0]);
+        assert_eq!(running[9], [7, 5]);
+    }
+}
@@ -1,19 +1,357 @@
+mod aggregate;
+mod follow;
+mod profile;
+
+use aggregate::{Aggregate, AggregateWindow};
+use aoc_common::explain;
+use aoc_common::input::{records, Source};
+use follow::Follow;
+use profile::{Format, Profiler};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufReader;
+
 const FILE_PATH: &str = "input.txt";
+/// Environment variable printing the depth profile after the answers, as `text` or `json`
+const REPORT_VAR: &str = "DAY_01_REPORT";
+/// Environment variable setting the window of the depth profile and of the aggregate, 1 by default
+const WINDOW_VAR: &str = "DAY_01_WINDOW";
+/// Largest window, every window size keeping as many readings in memory
+const MAX_WINDOW: usize = 1_000_000;
+/// Environment variable following the input file as readings are appended, printing the running
+/// counts
+const FOLLOW_VAR: &str = "DAY_01_FOLLOW";
+/// Environment variable counting the increases of another window aggregate than the sum
+const AGGREGATE_VAR: &str = "DAY_01_AGGREGATE";
+
+/// How the sum of a window compares to the one of the previous window
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Change {
+    Increase,
+    Decrease,
+    Flat,
+}
+
+impl Change {
+    fn of(delta: i64) -> Self {
+        match delta.cmp(&0) {
+            Ordering::Greater => Self::Increase,
+            Ordering::Less => Self::Decrease,
+            Ordering::Equal => Self::Flat,
+        }
+    }
+
+    fn of_f64(delta: f64) -> Self {
+        match delta.total_cmp(&0.) {
+            Ordering::Greater => Self::Increase,
+            Ordering::Less => Self::Decrease,
+            Ordering::Equal => Self::Flat,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Increase => write!(f, "increase"),
+            Self::Decrease => write!(f, "decrease"),
+            Self::Flat => write!(f, "flat"),
+        }
+    }
+}
+
+/// A reading and how it changed every window it completes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Event<const N: usize> {
+    index: usize,
+    reading: u32,
+    /// Change of the sum of the window of every size, `None` until enough readings were seen
+    deltas: [Option<i64>; N],
+}
+
+impl<const N: usize> Event<N> {
+    fn changes(&self) -> [Option<Change>; N] {
+        self.deltas.map(|delta| delta.map(Change::of))
+    }
+}
+
+/// Iterator adapter comparing sliding windows of readings, see [`DepthWindows::depth_windows`]
+#[derive(Debug, Clone)]
+struct Windows<I, const N: usize> {
+    readings: I,
+    sizes: [usize; N],
+    /// Ring buffer of the last readings, as many as the largest window
+    previous: VecDeque<u32>,
+    capacity: usize,
+    index: usize,
+}
+
+impl<I: Iterator<Item = u32>, const N: usize> Iterator for Windows<I, N> {
+    type Item = Event<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reading = self.readings.next()?;
+        let len = self.previous.len();
+        // Consecutive windows share every reading but the oldest of the first and the newest of
+        // the second, comparing these two compares the window sums
+        let deltas = self.sizes.map(|size| {
+            (len >= size).then(|| i64::from(reading) - i64::from(self.previous[len - size]))
+        });
+        if len == self.capacity {
+            self.previous.pop_front();
+        }
+        self.previous.push_back(reading);
+        let index = self.index;
+        self.index += 1;
+        Some(Event {
+            index,
+            reading,
+            deltas,
+        })
+    }
+}
+
+trait DepthWindows: Iterator<Item = u32> + Sized {
+    /// Yields an event per reading comparing the windows of every size it completes with the
+    /// previous ones, only keeping the last readings so it runs over unbounded inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if a window size is 0
+    fn depth_windows<const N: usize>(self, sizes: [usize; N]) -> Windows<Self, N> {
+        assert!(sizes.iter().all(|size| *size > 0), "Empty window");
+        let capacity = sizes.iter().max().copied().unwrap_or_default();
+        Windows {
+            readings: self,
+            sizes,
+            previous: VecDeque::with_capacity(capacity),
+            capacity,
+            index: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u32>> DepthWindows for I {}
+
+/// Counts the increases of every window size
+fn increasing_counts<const N: usize>(events: impl Iterator<Item = Event<N>>) -> [usize; N] {
+    events.fold([0; N], |mut counts, event| {
+        count_increases(&mut counts, &event);
+        counts
+    })
+}
+
+/// Adds the increases of `event` to the `counts` of every window size
+fn count_increases<const N: usize>(counts: &mut [usize; N], event: &Event<N>) {
+    for (count, change) in counts.iter_mut().zip(event.changes()) {
+        if change == Some(Change::Increase) {
+            *count += 1;
+        }
+    }
+}
+
+/// Prints the running counts as readings are appended to the input file, until an invalid one
+fn follow(source: &Source) -> Result<(), String> {
+    let Source::Path(path) = source else {
+        return Err(String::from("Only an input file can be followed"));
+    };
+    let mut error = None;
+    let readings = records(BufReader::new(Follow::open(path)?))
+        .map_while(|reading| reading.map_err(|e| error = Some(e)).ok());
+    let mut counts = [0; 2];
+    for (event, reading) in readings.depth_windows([1, 3]).zip(1..) {
+        count_increases(&mut counts, &event);
+        println!(
+            "{} readings, Part 1: {}, Part 2: {}",
+            reading, counts[0], counts[1]
+        );
+    }
+    error.map_or(Ok(()), Err)
+}
 
-fn get_increasing_count(values: &[u32], delta: usize) -> usize {
-    values
-        .iter()
-        .enumerate()
-        .filter(|(i, v)| *v < values.get(i + delta).unwrap_or(v))
-        .count()
+/// Options of the day, set by environment variables
+struct Options {
+    report: Option<Format>,
+    window: usize,
+    follow: bool,
+    /// The aggregate with its name, `None` being the sum
+    aggregate: Option<(String, Option<Box<dyn Aggregate>>)>,
+}
+
+impl Options {
+    fn from_env() -> Result<Self, String> {
+        Self::parse(|name| std::env::var(name).ok())
+    }
+
+    /// Options read from `var`, errors naming the offending variable
+    fn parse(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let report = var(REPORT_VAR)
+            .map(|format| format.parse())
+            .transpose()
+            .map_err(|e| format!("{}: {}", REPORT_VAR, e))?;
+        let window = var(WINDOW_VAR).map_or(Ok(1), |window| {
+            window
+                .parse()
+                .ok()
+                .filter(|window| (1..=MAX_WINDOW).contains(window))
+                .ok_or_else(|| {
+                    format!(
+                        "{}: Invalid window `{}`, expected 1 to {}",
+                        WINDOW_VAR, window, MAX_WINDOW
+                    )
+                })
+        })?;
+        let aggregate = var(AGGREGATE_VAR)
+            .map(|name| aggregate::parse(&name).map(|aggregate| (name, aggregate)))
+            .transpose()
+            .map_err(|e| format!("{}: {}", AGGREGATE_VAR, e))?;
+        Ok(Self {
+            report,
+            window,
+            follow: var(FOLLOW_VAR).is_some_and(|v| v != "0"),
+            aggregate,
+        })
+    }
+}
+
+fn run(options: Options) -> Result<(), String> {
+    if options.follow {
+        return follow(&aoc_common::source!(FILE_PATH));
+    }
+    let window = options.window;
+    let mut profiler = options.report.map(|_| Profiler::new(window));
+    let (aggregate_name, aggregate) = options.aggregate.unzip();
+    // The sum is `None`, its changes are the ones of the window in `depth_windows`
+    let mut aggregate = aggregate
+        .map(|aggregate| aggregate.map(|aggregate| AggregateWindow::new(window, aggregate)));
+    let mut aggregate_increases = 0;
+    let mut error = None;
+    let readings = records(aoc_common::reader!(FILE_PATH)?)
+        .map_while(|reading| reading.map_err(|e| error = Some(e)).ok());
+    let events = readings.depth_windows([1, 3, window]).inspect(|event| {
+        // The first readings have no previous window to compare with
+        let [change, window_change, _] = event
+            .changes()
+            .map(|c| c.map_or_else(|| String::from("none"), |c| c.to_string()));
+        explain!(
+            "reading",
+            index = event.index,
+            depth = event.reading,
+            change = change,
+            window_change = window_change
+        );
+        let aggregate_change = match aggregate.as_mut() {
+            Some(Some(aggregate)) => aggregate.push(event.reading),
+            Some(None) => event.changes()[2],
+            None => None,
+        };
+        if aggregate_change == Some(Change::Increase) {
+            aggregate_increases += 1;
+        }
+        if let Some(profiler) = profiler.as_mut() {
+            profiler.push(event.index, event.deltas[2]);
+        }
+    });
+    let [part_1, part_2, _] = increasing_counts(events);
+    if let Some(e) = error {
+        return Err(e);
+    }
+    println!("Part 1: {}", part_1);
+    println!("Part 2: {}", part_2);
+    if let Some(name) = aggregate_name {
+        println!(
+            "Increases of the {} of {} readings: {}",
+            name, window, aggregate_increases
+        );
+    }
+    if let Some((format, profiler)) = options.report.zip(profiler) {
+        let profile = profiler.finish();
+        match format {
+            Format::Text => println!("{}", profile),
+            Format::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&profile).map_err(|e| e.to_string())?
+            ),
+        }
+    }
+    Ok(())
 }
 
 fn main() {
-    let file_content: Vec<u32> = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .lines()
-        .map(|str| str.parse().unwrap())
-        .collect();
-    println!("Part 1: {}", get_increasing_count(&file_content, 1));
-    println!("Part 2: {}", get_increasing_count(&file_content, 3));
+    if let Err(e) = Options::from_env().and_then(run) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const READINGS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_increasing_counts() {
+        let events = READINGS.into_iter().depth_windows([1, 3]);
+        assert_eq!(increasing_counts(events), [7, 5]);
+    }
+
+    #[test]
+    fn test_events() {
+        let changes: Vec<_> = READINGS
+            .into_iter()
+            .depth_windows([3])
+            .map(|event| event.changes()[0])
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                None,
+                None,
+                None,
+                Some(Change::Increase),
+                Some(Change::Flat),
+                Some(Change::Decrease),
+                Some(Change::Increase),
+                Some(Change::Increase),
+                Some(Change::Increase),
+                Some(Change::Increase),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let options = |vars: &[(&str, &str)]| {
+            Options::parse(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| (*value).to_string())
+            })
+        };
+        let defaults = options(&[]).unwrap();
+        assert_eq!((defaults.window, defaults.follow), (1, false));
+        let set = options(&[(WINDOW_VAR, "4"), (AGGREGATE_VAR, "median")]).unwrap();
+        assert_eq!(set.window, 4);
+        assert_eq!(
+            set.aggregate.map(|(name, _)| name),
+            Some(String::from("median"))
+        );
+        assert_eq!(
+            options(&[(WINDOW_VAR, "0")]).err(),
+            Some(String::from(
+                "DAY_01_WINDOW: Invalid window `0`, expected 1 to 1000000"
+            ))
+        );
+        assert!(options(&[(WINDOW_VAR, "1000000")]).is_ok());
+        assert_eq!(
+            options(&[(WINDOW_VAR, "18446744073709551615")]).err(),
+            Some(String::from(
+                "DAY_01_WINDOW: Invalid window `18446744073709551615`, expected 1 to 1000000"
+            ))
+        );
+        assert!(options(&[(WINDOW_VAR, "three")]).is_err());
+        assert!(options(&[(REPORT_VAR, "xml")]).is_err());
+    }
 }
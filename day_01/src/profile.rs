@@ -0,0 +1,222 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Width of the step delta histogram buckets
+const BUCKET: i64 = 10;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown report format `{}`, expected text or json",
+                s
+            )),
+        }
+    }
+}
+
+/// Consecutive window steps, `start` being the index of the reading completing the first one
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Run {
+    /// Extends the run with the step at `index` if it `continues` it, updating `longest`
+    const fn extend(&mut self, longest: &mut Self, index: usize, continues: bool) {
+        if !continues {
+            self.length = 0;
+            return;
+        }
+        if self.length == 0 {
+            self.start = index;
+        }
+        self.length += 1;
+        if self.length > longest.length {
+            *longest = *self;
+        }
+    }
+}
+
+/// A window step, `delta` being the change of the window sum
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Step {
+    pub index: usize,
+    pub delta: i64,
+}
+
+/// Shape of the sea floor seen through windows of `window` readings
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct DepthProfile {
+    pub window: usize,
+    pub readings: usize,
+    pub longest_increase: Run,
+    pub longest_plateau: Run,
+    pub plateaus: usize,
+    /// Most negative step, the sub rising towards the surface
+    pub largest_drop: Option<Step>,
+    /// Most positive step, its delta divided by the window is the depth gained per reading
+    pub deepest_descent: Option<Step>,
+    /// Step counts by bucket of [`BUCKET`] deltas, keyed by the lowest delta of the bucket
+    pub histogram: BTreeMap<i64, usize>,
+}
+
+impl DepthProfile {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn descent_rate(&self) -> Option<f64> {
+        self.deepest_descent
+            .map(|step| step.delta as f64 / self.window as f64)
+    }
+}
+
+/// Computes a [`DepthProfile`] in a single pass, fed with the window steps of every reading
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    profile: DepthProfile,
+    increase: Run,
+    plateau: Run,
+}
+
+impl Profiler {
+    pub fn new(window: usize) -> Self {
+        Self {
+            profile: DepthProfile {
+                window,
+                readings: 0,
+                longest_increase: Run::default(),
+                longest_plateau: Run::default(),
+                plateaus: 0,
+                largest_drop: None,
+                deepest_descent: None,
+                histogram: BTreeMap::new(),
+            },
+            increase: Run::default(),
+            plateau: Run::default(),
+        }
+    }
+
+    /// Adds the reading at `index`, `delta` being `None` until it completes a second window
+    pub fn push(&mut self, index: usize, delta: Option<i64>) {
+        let profile = &mut self.profile;
+        profile.readings += 1;
+        let Some(delta) = delta else {
+            return;
+        };
+        let step = Step { index, delta };
+        self.increase
+            .extend(&mut profile.longest_increase, index, delta > 0);
+        if delta == 0 && self.plateau.length == 0 {
+            profile.plateaus += 1;
+        }
+        self.plateau
+            .extend(&mut profile.longest_plateau, index, delta == 0);
+        if delta < 0 && profile.largest_drop.is_none_or(|drop| delta < drop.delta) {
+            profile.largest_drop = Some(step);
+        }
+        if delta > 0
+            && profile
+                .deepest_descent
+                .is_none_or(|descent| delta > descent.delta)
+        {
+            profile.deepest_descent = Some(step);
+        }
+        *profile
+            .histogram
+            .entry(delta.div_euclid(BUCKET) * BUCKET)
+            .or_default() += 1;
+    }
+
+    pub fn finish(self) -> DepthProfile {
+        self.profile
+    }
+}
+
+impl Display for DepthProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Depth profile of {} readings, window of {}",
+            self.readings, self.window
+        )?;
+        writeln!(
+            f,
+            "Longest increase: {} steps from reading {}",
+            self.longest_increase.length, self.longest_increase.start
+        )?;
+        writeln!(
+            f,
+            "Longest plateau: {} steps from reading {} ({} plateaus)",
+            self.longest_plateau.length, self.longest_plateau.start, self.plateaus
+        )?;
+        match self.largest_drop {
+            Some(drop) => writeln!(f, "Largest drop: {} at reading {}", -drop.delta, drop.index)?,
+            None => writeln!(f, "Largest drop: none")?,
+        }
+        match (self.deepest_descent, self.descent_rate()) {
+            (Some(descent), Some(rate)) => writeln!(
+                f,
+                "Deepest descent: {:.2} per reading at reading {}",
+                rate, descent.index
+            )?,
+            _ => writeln!(f, "Deepest descent: none")?,
+        }
+        write!(f, "Step deltas:")?;
+        for (bucket, count) in &self.histogram {
+            write!(f, "\n  {:>6}..{:<6} {}", bucket, bucket + BUCKET, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile() {
+        let deltas = [None, Some(1), Some(8), Some(0), Some(0), Some(-10), Some(7)];
+        let mut profiler = Profiler::new(1);
+        for (index, delta) in deltas.into_iter().enumerate() {
+            profiler.push(index, delta);
+        }
+        let profile = profiler.finish();
+        assert_eq!(profile.readings, 7);
+        assert_eq!(
+            profile.longest_increase,
+            Run {
+                start: 1,
+                length: 2
+            }
+        );
+        assert_eq!(
+            profile.longest_plateau,
+            Run {
+                start: 3,
+                length: 2
+            }
+        );
+        assert_eq!(profile.plateaus, 1);
+        assert_eq!(
+            profile.largest_drop,
+            Some(Step {
+                index: 5,
+                delta: -10
+            })
+        );
+        assert_eq!(profile.descent_rate(), Some(8.0));
+        assert_eq!(profile.histogram, BTreeMap::from([(-10, 1), (0, 5)]));
+    }
+}
@@ -1,55 +1,179 @@
+mod planner;
+mod script;
+mod submarine;
+mod trajectory;
+
+use planner::Planner;
+use script::Script;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
 use std::str::FromStr;
+use submarine::{Aim, BreachPolicy, Direct, Navigator, Submarine};
+use trajectory::Trajectory;
 
 const FILE_PATH: &str = "input.txt";
+/// Environment variable exporting the trajectories of both semantics to a directory, as
+/// `direct.csv`, `direct.svg`, `aim.csv` and `aim.svg`
+const TRAJECTORY_VAR: &str = "DAY_02_TRAJECTORY";
+/// Environment variable choosing what to do when the depth or the aim would become negative:
+/// `clamp` (by default), `error` or `allow`
+const BREACH_VAR: &str = "DAY_02_BREACH";
+/// Environment variable planning the shortest route to `X,DEPTH[,MAX_COMMANDS[,MAX_VALUE]]` under
+/// the aim semantics, printed as a script instead of solving the input
+const PLAN_VAR: &str = "DAY_02_PLAN";
+/// Default bound of the plans: number of commands, and value of a command
+const PLAN_COMMANDS: usize = 20;
+const PLAN_VALUE: Position = 9;
+
+#[cfg(feature = "wide")]
+type Position = i128;
+#[cfg(not(feature = "wide"))]
+type Position = i64;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum MoveDirection {
-    Forward(u32),
-    Down(u32),
-    Up(u32),
+    Forward(Position),
+    Back(Position),
+    Down(Position),
+    Up(Position),
 }
 
-impl FromStr for MoveDirection {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (action, value) = s
-            .split_once(' ')
-            .ok_or(format!("Wrong format: `{}` cannot be parsed", s))?;
-        let value = value.parse::<u32>().map_err(|e| e.to_string())?;
+impl MoveDirection {
+    /// Command from its action and value tokens
+    fn from_tokens(action: &str, value: &str) -> Result<Self, String> {
+        let value = value.parse::<Position>().map_err(|e| e.to_string())?;
+        if value < 0 {
+            return Err(format!("Negative value: {} {}", action, value));
+        }
         match action {
             "down" => Ok(Self::Down(value)),
             "forward" => Ok(Self::Forward(value)),
+            "back" => Ok(Self::Back(value)),
             "up" => Ok(Self::Up(value)),
-            _ => Err(format!("Wrong direction: {}", s)),
+            _ => Err(format!("Wrong direction: {} {}", action, value)),
+        }
+    }
+}
+
+impl FromStr for MoveDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [action, value] => Self::from_tokens(action, value),
+            _ => Err(format!("Wrong format: `{}` cannot be parsed", s)),
+        }
+    }
+}
+
+impl Display for MoveDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forward(v) => write!(f, "forward {}", v),
+            Self::Back(v) => write!(f, "back {}", v),
+            Self::Down(v) => write!(f, "down {}", v),
+            Self::Up(v) => write!(f, "up {}", v),
+        }
+    }
+}
+
+/// Prints the route to the target, or why there is none
+fn plan(spec: &str) -> Result<bool, String> {
+    let fields: Vec<&str> = spec.split(',').map(str::trim).collect();
+    let error = |e: String| format!("{}: {}", PLAN_VAR, e);
+    let (target, bound) = match fields.as_slice() {
+        [x, depth, bound @ ..] if bound.len() <= 2 => ([x, depth], bound),
+        _ => {
+            return Err(error(format!(
+                "Wrong target `{}`, expected X,DEPTH[,MAX_COMMANDS[,MAX_VALUE]]",
+                spec
+            )))
+        }
+    };
+    let [x, depth] = target.map(|v| v.parse::<Position>().map_err(|e| error(e.to_string())));
+    let max_commands = bound
+        .first()
+        .map_or(Ok(PLAN_COMMANDS), |max_commands| max_commands.parse())
+        .map_err(|e| error(e.to_string()))?;
+    let max_value = bound
+        .get(1)
+        .map_or(Ok(PLAN_VALUE), |max_value| max_value.parse())
+        .map_err(|e| error(e.to_string()))?;
+    let planner = Planner::new(max_value, max_commands).map_err(error)?;
+    match planner.plan(x?, depth?) {
+        Ok(route) => {
+            for command in route {
+                println!("{}", command);
+            }
+            Ok(true)
+        }
+        Err(proof) => {
+            println!("{}", proof);
+            Ok(false)
         }
     }
 }
 
-fn part_1(directions: impl Iterator<Item = MoveDirection>) {
-    let (x, y) = directions.fold((0, 0), |(x, y), dir| match dir {
-        MoveDirection::Forward(v) => (x + v, y),
-        MoveDirection::Down(v) => (x, y + v),
-        MoveDirection::Up(v) => (x, y.saturating_sub(v)),
-    });
-    println!("Part 1. Final pos = ({}, {}), Result = {}", x, y, x * y);
+fn print_result(part: u8, submarine: &Submarine<impl Navigator>) {
+    let state = submarine.state();
+    println!(
+        "Part {}. Final pos = ({}, {}), Result = {}",
+        part,
+        state.x,
+        state.depth,
+        state.result()
+    );
+    for breach in submarine.breaches() {
+        println!("Part {}. {}", part, breach);
+    }
 }
 
-fn part_2(directions: impl Iterator<Item = MoveDirection>) {
-    let (x, y, _aim) = directions.fold((0, 0, 0), |(x, y, aim), dir| match dir {
-        MoveDirection::Forward(v) => (x + v, y + aim * v, aim),
-        MoveDirection::Down(v) => (x, y, aim + v),
-        MoveDirection::Up(v) => (x, y, aim.saturating_sub(v)),
-    });
-    println!("Part 2. Final pos = ({}, {}), Result = {}", x, y, x * y);
+fn run() -> Result<(), String> {
+    let policy: BreachPolicy = match std::env::var(BREACH_VAR) {
+        Ok(policy) => policy
+            .parse()
+            .map_err(|e| format!("{}: {}", BREACH_VAR, e))?,
+        Err(_) => BreachPolicy::default(),
+    };
+    let script = Script::parse(&aoc_common::input!(FILE_PATH)?)?;
+    // The same commands under both semantics
+    let (mut direct, mut aim) = (Submarine::new(Direct, policy), Submarine::new(Aim, policy));
+    let export_dir = std::env::var_os(TRAJECTORY_VAR).map(PathBuf::from);
+    let mut trajectories = export_dir
+        .as_ref()
+        .map(|_| [Trajectory::new(), Trajectory::new()]);
+    for (line, command) in script.commands() {
+        let states = [
+            direct
+                .execute(line, command)
+                .map_err(|breach| format!("Part 1. {}", breach))?,
+            aim.execute(line, command)
+                .map_err(|breach| format!("Part 2. {}", breach))?,
+        ];
+        if let Some(trajectories) = trajectories.as_mut() {
+            for (trajectory, state) in trajectories.iter_mut().zip(states) {
+                trajectory.record(state);
+            }
+        }
+    }
+    if let Some((dir, trajectories)) = export_dir.zip(trajectories) {
+        for (name, trajectory) in ["direct", "aim"].into_iter().zip(trajectories) {
+            trajectory.export(&dir, name)?;
+        }
+    }
+    print_result(1, &direct);
+    print_result(2, &aim);
+    Ok(())
 }
 
 fn main() {
-    let file_content: Vec<MoveDirection> = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .split('\n')
-        .map(|str| MoveDirection::from_str(str).unwrap())
-        .collect();
-    part_1(file_content.iter().copied());
-    part_2(file_content.into_iter());
+    let result = std::env::var(PLAN_VAR).map_or_else(|_| run().map(|()| true), |spec| plan(&spec));
+    match result {
+        Ok(true) => (),
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
 }
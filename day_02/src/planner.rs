@@ -0,0 +1,222 @@
+use crate::submarine::{Aim, Navigator, State};
+use crate::{MoveDirection, Position};
+use std::collections::HashMap;
+
+/// Largest command value searched, each state trying every value up to it
+pub const MAX_VALUE: Position = 1000;
+
+/// Plans routes under the aim rules, the depth and the aim never going negative so the route
+/// is valid under every breach policy
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Planner {
+    /// Largest value of a command, puzzle inputs using 1 to 9
+    max_value: Position,
+    /// Longest route searched
+    max_commands: usize,
+}
+
+impl Planner {
+    /// Planner of routes of at most `max_commands` commands, each of value 1 to `max_value`
+    ///
+    /// # Errors
+    ///
+    /// Fails if `max_value` isn't within 1 to [`MAX_VALUE`]
+    pub fn new(max_value: Position, max_commands: usize) -> Result<Self, String> {
+        if !(1..=MAX_VALUE).contains(&max_value) {
+            return Err(format!(
+                "Invalid command value {}, expected 1 to {}",
+                max_value, MAX_VALUE
+            ));
+        }
+        Ok(Self {
+            max_value,
+            max_commands,
+        })
+    }
+
+    /// Shortest route ending at the horizontal position `x` and the `depth`, among the routes
+    /// never aiming upwards: a negative aim being a breach, shorter routes through one aren't
+    /// searched
+    ///
+    /// # Errors
+    ///
+    /// Fails if the target can't be reached, or if every route of at most `max_commands`
+    /// commands never aiming upwards was searched without reaching it
+    pub fn plan(&self, x: Position, depth: Position) -> Result<Vec<MoveDirection>, String> {
+        if x < 0 || depth < 0 {
+            return Err(format!(
+                "({}, {}) is unreachable: the submarine never moves backwards nor above the surface",
+                x, depth
+            ));
+        }
+        if x == 0 && depth != 0 {
+            return Err(format!(
+                "(0, {}) is unreachable: the depth only changes moving forward",
+                depth
+            ));
+        }
+        // Breadth first search, all the routes of a layer having as many commands
+        let mut parents: HashMap<State, (State, MoveDirection)> = HashMap::new();
+        let mut layer = vec![State::default()];
+        for commands in 0..=self.max_commands {
+            if let Some(state) = layer.iter().find(|s| s.x == x && s.depth == depth) {
+                return Ok(Self::route(&parents, *state));
+            }
+            // Commands left after the next one, none when the bound is reached
+            let Some(left) = (self.max_commands - commands).checked_sub(1) else {
+                break;
+            };
+            let mut next_layer = vec![];
+            for state in layer {
+                for command in self.moves(state, x, depth) {
+                    let next = Aim.navigate(state, command);
+                    // Every forward command moves at most `max_value`
+                    let forwards = (x - next.x)
+                        .unsigned_abs()
+                        .div_ceil(self.max_value.unsigned_abs());
+                    let Some(downs) = usize::try_from(forwards)
+                        .ok()
+                        .and_then(|f| left.checked_sub(f))
+                    else {
+                        continue;
+                    };
+                    // Deepest reachable: every command left but the forward ones raising the aim
+                    let aim = Position::try_from(downs)
+                        .unwrap_or(Position::MAX)
+                        .saturating_mul(self.max_value)
+                        .saturating_add(next.aim);
+                    let deepest = (x - next.x).saturating_mul(aim).saturating_add(next.depth);
+                    let dead_end = next.x == x && next.depth != depth;
+                    if dead_end
+                        || deepest < depth
+                        || next == State::default()
+                        || parents.contains_key(&next)
+                    {
+                        continue;
+                    }
+                    parents.insert(next, (state, command));
+                    next_layer.push(next);
+                }
+            }
+            layer = next_layer;
+        }
+        Err(format!(
+            "No route to ({}, {}) within {} commands of at most {} never aiming upwards",
+            x, depth, self.max_commands, self.max_value
+        ))
+    }
+
+    /// Commands keeping the submarine on the way to the target: never past it, and never aiming
+    /// deeper than the depth left
+    fn moves(
+        &self,
+        state: State,
+        x: Position,
+        depth: Position,
+    ) -> impl Iterator<Item = MoveDirection> {
+        let forwards = (1..=self.max_value.min(x - state.x))
+            .filter(move |v| {
+                state
+                    .aim
+                    .checked_mul(*v)
+                    .and_then(|dive| dive.checked_add(state.depth))
+                    .is_some_and(|next| next <= depth)
+            })
+            .map(MoveDirection::Forward);
+        let downs =
+            (1..=self.max_value.min(depth - state.depth - state.aim)).map(MoveDirection::Down);
+        let ups = (1..=self.max_value.min(state.aim)).map(MoveDirection::Up);
+        forwards.chain(downs).chain(ups)
+    }
+
+    fn route(
+        parents: &HashMap<State, (State, MoveDirection)>,
+        mut state: State,
+    ) -> Vec<MoveDirection> {
+        let mut route = vec![];
+        while let Some((parent, command)) = parents.get(&state) {
+            route.push(*command);
+            state = *parent;
+        }
+        route.reverse();
+        route
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::submarine::{BreachPolicy, Submarine};
+
+    const PLANNER: Planner = Planner {
+        max_value: 9,
+        max_commands: 10,
+    };
+
+    fn execute(route: &[MoveDirection]) -> State {
+        let mut submarine = Submarine::new(Aim, BreachPolicy::Error);
+        for (line, command) in (1..).zip(route) {
+            submarine.execute(line, *command).unwrap();
+        }
+        submarine.state()
+    }
+
+    #[test]
+    fn test_plan() {
+        let route = PLANNER.plan(15, 60).unwrap();
+        let state = execute(&route);
+        assert_eq!((state.x, state.depth), (15, 60));
+        assert_eq!(route.len(), 3);
+        assert_eq!(PLANNER.plan(0, 0), Ok(vec![]));
+        assert_eq!(PLANNER.plan(7, 0), Ok(vec![MoveDirection::Forward(7)]));
+    }
+
+    #[test]
+    fn test_large_values() {
+        assert!(PLANNER.plan(Position::MAX, 1).is_err());
+        assert!(PLANNER.plan(1, Position::MAX).is_err());
+        assert_eq!(
+            Planner::new(Position::MAX, 20),
+            Err(format!(
+                "Invalid command value {}, expected 1 to 1000",
+                Position::MAX
+            ))
+        );
+        assert!(Planner::new(0, 20).is_err());
+        let route = Planner::new(MAX_VALUE, 2).unwrap().plan(1000, 500_000);
+        assert_eq!(
+            route,
+            Ok(vec![MoveDirection::Down(500), MoveDirection::Forward(1000)])
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        assert!(PLANNER.plan(0, 3).is_err());
+        assert!(PLANNER.plan(-1, 0).is_err());
+        // The aim needs two commands to reach 10
+        let planner = Planner {
+            max_value: 9,
+            max_commands: 2,
+        };
+        assert_eq!(
+            planner.plan(1, 10),
+            Err(String::from(
+                "No route to (1, 10) within 2 commands of at most 9 never aiming upwards"
+            ))
+        );
+        assert_eq!(PLANNER.plan(1, 10).map(|route| route.len()), Ok(3));
+        // Only the start is searched without any command
+        let planner = Planner {
+            max_value: 9,
+            max_commands: 0,
+        };
+        assert_eq!(planner.plan(0, 0), Ok(vec![]));
+        assert_eq!(
+            planner.plan(5, 0),
+            Err(String::from(
+                "No route to (5, 0) within 0 commands of at most 9 never aiming upwards"
+            ))
+        );
+    }
+}
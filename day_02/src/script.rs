@@ -0,0 +1,191 @@
+//! Submarine scripts: one command per line, `#` comments, `repeat N { ... }` blocks and
+//! `macro NAME { ... }` definitions, a macro being called by its name once defined.
+//!
+//! ```text
+//! macro dive {
+//!     down 5
+//!     forward 2 # steep
+//! }
+//! repeat 3 {
+//!     dive
+//!     back 1
+//! }
+//! ```
+
+use crate::MoveDirection;
+use std::collections::HashMap;
+use std::iter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Command { line: usize, command: MoveDirection },
+    Repeat { count: usize, body: Vec<Self> },
+    Call { name: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    macros: HashMap<String, Vec<Statement>>,
+    body: Vec<Statement>,
+}
+
+/// A block being parsed, with the line opening it for errors
+enum Block {
+    Repeat { line: usize, count: usize },
+    Macro { line: usize, name: String },
+}
+
+const KEYWORDS: [&str; 6] = ["repeat", "macro", "forward", "back", "down", "up"];
+
+impl Script {
+    /// Parses a script, errors naming the offending line
+    pub fn parse(script: &str) -> Result<Self, String> {
+        let mut macros = HashMap::new();
+        // The blocks being parsed, with the statements of the block enclosing each of them
+        let mut blocks: Vec<(Block, Vec<Statement>)> = vec![];
+        let mut statements = vec![];
+        for (line, text) in (1..).zip(script.lines()) {
+            let error = |message: String| format!("Line {}: {}", line, message);
+            let text = text.split_once('#').map_or(text, |(code, _)| code);
+            match text.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => (),
+                ["}"] => {
+                    let (block, enclosing) = blocks
+                        .pop()
+                        .ok_or_else(|| error(String::from("Unexpected `}`")))?;
+                    let body = std::mem::replace(&mut statements, enclosing);
+                    match block {
+                        Block::Repeat { count, .. } => {
+                            statements.push(Statement::Repeat { count, body });
+                        }
+                        Block::Macro { name, .. } => {
+                            macros.insert(name, body);
+                        }
+                    }
+                }
+                ["repeat", count, "{"] => {
+                    let count = count
+                        .parse()
+                        .map_err(|_| error(format!("Invalid repeat count `{}`", count)))?;
+                    let block = Block::Repeat { line, count };
+                    blocks.push((block, std::mem::take(&mut statements)));
+                }
+                ["macro", name, "{"] => {
+                    if !blocks.is_empty() {
+                        return Err(error(String::from("Macros are defined at the top level")));
+                    }
+                    if KEYWORDS.contains(name) || macros.contains_key(*name) {
+                        return Err(error(format!("`{}` is already defined", name)));
+                    }
+                    let name = (*name).to_string();
+                    blocks.push((Block::Macro { line, name }, std::mem::take(&mut statements)));
+                }
+                [name] if macros.contains_key(*name) => statements.push(Statement::Call {
+                    name: (*name).to_string(),
+                }),
+                [name] if !KEYWORDS.contains(name) => {
+                    return Err(error(format!("Unknown macro `{}`", name)))
+                }
+                [action, value] => statements.push(Statement::Command {
+                    line,
+                    command: MoveDirection::from_tokens(action, value).map_err(error)?,
+                }),
+                _ => {
+                    return Err(error(format!(
+                        "Wrong format: `{}` cannot be parsed",
+                        text.trim()
+                    )))
+                }
+            }
+        }
+        if let Some((Block::Repeat { line, .. } | Block::Macro { line, .. }, _)) = blocks.pop() {
+            return Err(format!("Line {}: Unclosed block", line));
+        }
+        Ok(Self {
+            macros,
+            body: statements,
+        })
+    }
+
+    /// Expands the repeat blocks and the macro calls into the command stream, each command with
+    /// the line it is written on
+    pub fn commands(&self) -> impl Iterator<Item = (usize, MoveDirection)> + '_ {
+        self.expand(&self.body)
+    }
+
+    fn expand<'a>(
+        &'a self,
+        statements: &'a [Statement],
+    ) -> Box<dyn Iterator<Item = (usize, MoveDirection)> + 'a> {
+        Box::new(statements.iter().flat_map(
+            move |statement| -> Box<dyn Iterator<Item = (usize, MoveDirection)> + 'a> {
+                match statement {
+                    Statement::Command { line, command } => Box::new(iter::once((*line, *command))),
+                    Statement::Repeat { count, body, .. } => {
+                        Box::new((0..*count).flat_map(move |_| self.expand(body)))
+                    }
+                    // Calls are only parsed once their macro is defined
+                    Statement::Call { name, .. } => self.expand(&self.macros[name]),
+                }
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let script = "# Descent\n\
+                      macro dive {\n    down 5\n    forward 2 # steep\n}\n\n\
+                      repeat 2 {\n    dive\n    repeat 2 {\n        back 1\n    }\n}\nup 3\n";
+        let commands: Vec<_> = Script::parse(script).unwrap().commands().collect();
+        assert_eq!(
+            commands,
+            [
+                (3, MoveDirection::Down(5)),
+                (4, MoveDirection::Forward(2)),
+                (10, MoveDirection::Back(1)),
+                (10, MoveDirection::Back(1)),
+                (3, MoveDirection::Down(5)),
+                (4, MoveDirection::Forward(2)),
+                (10, MoveDirection::Back(1)),
+                (10, MoveDirection::Back(1)),
+                (13, MoveDirection::Up(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let script = Script::parse("down  5\n\tforward\t2\n").unwrap();
+        assert_eq!(
+            script.commands().collect::<Vec<_>>(),
+            [(1, MoveDirection::Down(5)), (2, MoveDirection::Forward(2))]
+        );
+        assert_eq!("up\t3".parse(), Ok(MoveDirection::Up(3)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |script| Script::parse(script).unwrap_err();
+        assert_eq!(
+            error("forward 1\nsideways 2"),
+            "Line 2: Wrong direction: sideways 2"
+        );
+        assert_eq!(error("repeat 2 {\nup 1\n"), "Line 1: Unclosed block");
+        assert_eq!(
+            error("down 1 2"),
+            "Line 1: Wrong format: `down 1 2` cannot be parsed"
+        );
+        assert_eq!(error("up 1\n}"), "Line 2: Unexpected `}`");
+        assert_eq!(error("repeat x {\n}"), "Line 1: Invalid repeat count `x`");
+        assert_eq!(error("macro a {\na\n}"), "Line 2: Unknown macro `a`");
+        assert_eq!(
+            error("repeat 1 {\nmacro a {\n}\n}"),
+            "Line 2: Macros are defined at the top level"
+        );
+    }
+}
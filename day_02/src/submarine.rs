@@ -0,0 +1,312 @@
+use crate::{MoveDirection, Position};
+use aoc_common::arith;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Where the submarine is, and where it is heading in aim mode. A negative depth is above the
+/// surface, a negative aim heads upwards
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct State {
+    pub x: Position,
+    pub depth: Position,
+    pub aim: Position,
+}
+
+impl State {
+    /// Horizontal position multiplied by the depth
+    pub fn result(self) -> Position {
+        arith!(Mul, self.x, self.depth)
+    }
+}
+
+/// Semantics of the commands, moving the submarine from one state to the next
+pub trait Navigator {
+    fn navigate(&self, state: State, command: MoveDirection) -> State;
+}
+
+/// User defined semantics
+impl<F: Fn(State, MoveDirection) -> State> Navigator for F {
+    fn navigate(&self, state: State, command: MoveDirection) -> State {
+        self(state, command)
+    }
+}
+
+/// `down` and `up` change the depth directly
+pub struct Direct;
+
+impl Navigator for Direct {
+    fn navigate(&self, state: State, command: MoveDirection) -> State {
+        match command {
+            MoveDirection::Forward(v) => State {
+                x: arith!(Add, state.x, v),
+                ..state
+            },
+            MoveDirection::Back(v) => State {
+                x: arith!(Sub, state.x, v),
+                ..state
+            },
+            MoveDirection::Down(v) => State {
+                depth: arith!(Add, state.depth, v),
+                ..state
+            },
+            MoveDirection::Up(v) => State {
+                depth: arith!(Sub, state.depth, v),
+                ..state
+            },
+        }
+    }
+}
+
+/// `down` and `up` change the aim, `forward` dives along it
+pub struct Aim;
+
+impl Navigator for Aim {
+    fn navigate(&self, state: State, command: MoveDirection) -> State {
+        match command {
+            MoveDirection::Forward(v) => State {
+                x: arith!(Add, state.x, v),
+                depth: arith!(Add, state.depth, arith!(Mul, state.aim, v)),
+                ..state
+            },
+            MoveDirection::Back(v) => State {
+                x: arith!(Sub, state.x, v),
+                depth: arith!(Sub, state.depth, arith!(Mul, state.aim, v)),
+                ..state
+            },
+            MoveDirection::Down(v) => State {
+                aim: arith!(Add, state.aim, v),
+                ..state
+            },
+            MoveDirection::Up(v) => State {
+                aim: arith!(Sub, state.aim, v),
+                ..state
+            },
+        }
+    }
+}
+
+/// What to do when a command leaves the depth or the aim negative
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum BreachPolicy {
+    /// Bring the coordinate back to 0
+    #[default]
+    Clamp,
+    /// Refuse the command
+    Error,
+    /// Keep the negative coordinate
+    Allow,
+}
+
+impl FromStr for BreachPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clamp" => Ok(Self::Clamp),
+            "error" => Ok(Self::Error),
+            "allow" => Ok(Self::Allow),
+            _ => Err(format!(
+                "Unknown breach policy `{}`, expected clamp, error or allow",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Coordinate {
+    Depth,
+    Aim,
+}
+
+/// A command taking the submarine above the surface, or aiming it upwards
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Breach {
+    /// Line of the command in its script
+    pub line: usize,
+    pub coordinate: Coordinate,
+    pub value: Position,
+}
+
+impl Display for Breach {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let coordinate = match self.coordinate {
+            Coordinate::Depth => "depth",
+            Coordinate::Aim => "aim",
+        };
+        write!(
+            f,
+            "Line {}: the command leaves the {} at {}",
+            self.line, coordinate, self.value
+        )
+    }
+}
+
+/// A submarine following commands under the semantics of its navigator
+pub struct Submarine<N> {
+    navigator: N,
+    policy: BreachPolicy,
+    state: State,
+    breaches: Vec<Breach>,
+}
+
+impl<N: Navigator> Submarine<N> {
+    pub const fn new(navigator: N, policy: BreachPolicy) -> Self {
+        Self {
+            navigator,
+            policy,
+            state: State {
+                x: 0,
+                depth: 0,
+                aim: 0,
+            },
+            breaches: Vec::new(),
+        }
+    }
+
+    pub const fn state(&self) -> State {
+        self.state
+    }
+
+    /// The commands which took the depth or the aim negative, or further below 0
+    pub fn breaches(&self) -> &[Breach] {
+        &self.breaches
+    }
+
+    /// Executes the command written on `line` of its script, returning the new state
+    ///
+    /// # Errors
+    ///
+    /// Fails with the breach when the command leaves the depth or the aim negative under the
+    /// [`BreachPolicy::Error`] policy, the submarine staying where it was
+    pub fn execute(&mut self, line: usize, command: MoveDirection) -> Result<State, Breach> {
+        let mut state = self.navigator.navigate(self.state, command);
+        for (coordinate, value, previous) in [
+            (Coordinate::Depth, &mut state.depth, self.state.depth),
+            (Coordinate::Aim, &mut state.aim, self.state.aim),
+        ] {
+            // Under the allow policy, a command bringing a negative coordinate back towards 0
+            // isn't a breach
+            if *value < previous.min(0) {
+                let breach = Breach {
+                    line,
+                    coordinate,
+                    value: *value,
+                };
+                match self.policy {
+                    BreachPolicy::Clamp => *value = 0,
+                    BreachPolicy::Error => return Err(breach),
+                    BreachPolicy::Allow => (),
+                }
+                self.breaches.push(breach);
+            }
+        }
+        self.state = state;
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMANDS: [MoveDirection; 6] = [
+        MoveDirection::Forward(5),
+        MoveDirection::Down(5),
+        MoveDirection::Forward(8),
+        MoveDirection::Up(3),
+        MoveDirection::Down(8),
+        MoveDirection::Forward(2),
+    ];
+
+    fn run(navigator: impl Navigator, commands: &[MoveDirection]) -> State {
+        let mut submarine = Submarine::new(navigator, BreachPolicy::Clamp);
+        for (line, command) in (1..).zip(commands) {
+            submarine.execute(line, *command).unwrap();
+        }
+        submarine.state()
+    }
+
+    #[test]
+    fn test_navigators() {
+        assert_eq!(run(Direct, &COMMANDS).result(), 150);
+        assert_eq!(run(Aim, &COMMANDS).result(), 900);
+    }
+
+    #[test]
+    fn test_replay() {
+        assert_eq!(
+            run(Aim, &COMMANDS[..3]),
+            State {
+                x: 13,
+                depth: 40,
+                aim: 5
+            }
+        );
+        // Going backwards when moving forward
+        let reverse = |state: State, command| match command {
+            MoveDirection::Forward(v) => State {
+                x: state.x - v,
+                ..state
+            },
+            _ => Direct.navigate(state, command),
+        };
+        assert_eq!(
+            run(reverse, &COMMANDS),
+            State {
+                x: -15,
+                depth: 10,
+                aim: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_breach_policies() {
+        let commands = [
+            MoveDirection::Down(2),
+            MoveDirection::Up(5),
+            MoveDirection::Down(1),
+            MoveDirection::Up(4),
+        ];
+        let breach = Breach {
+            line: 2,
+            coordinate: Coordinate::Depth,
+            value: -3,
+        };
+        let mut clamped = Submarine::new(Direct, BreachPolicy::Clamp);
+        let mut allowed = Submarine::new(Direct, BreachPolicy::Allow);
+        for (line, command) in (1..).zip(commands) {
+            clamped.execute(line, command).unwrap();
+            allowed.execute(line, command).unwrap();
+        }
+        let clamped_again = Breach { line: 4, ..breach };
+        assert_eq!(
+            (clamped.state().depth, clamped.breaches()),
+            (0, &[breach, clamped_again][..])
+        );
+        // Rising back towards the surface at line 3 isn't a breach, going further up at line 4 is
+        let further = Breach {
+            line: 4,
+            value: -6,
+            ..breach
+        };
+        assert_eq!(
+            (allowed.state().depth, allowed.breaches()),
+            (-6, &[breach, further][..])
+        );
+        let mut strict = Submarine::new(Aim, BreachPolicy::Error);
+        let results: Vec<_> = (1..)
+            .zip(commands)
+            .map(|(line, command)| strict.execute(line, command))
+            .collect();
+        assert_eq!(
+            results[1],
+            Err(Breach {
+                coordinate: Coordinate::Aim,
+                ..breach
+            })
+        );
+        assert_eq!(strict.state().aim, 3);
+    }
+}
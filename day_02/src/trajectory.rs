@@ -0,0 +1,110 @@
+use crate::submarine::State;
+use crate::Position;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Size of the exported SVG images, the route being stretched to fill them
+const SVG_SIZE: (u32, u32) = (800, 400);
+
+/// Every state of a submarine, from the surface to after each command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    states: Vec<State>,
+}
+
+impl Trajectory {
+    pub fn new() -> Self {
+        Self {
+            states: vec![State::default()],
+        }
+    }
+
+    pub fn record(&mut self, state: State) {
+        self.states.push(state);
+    }
+
+    /// One row per state, the first one being the start
+    pub fn to_csv(&self) -> String {
+        self.states.iter().enumerate().fold(
+            String::from("step,x,depth,aim\n"),
+            |mut csv, (step, state)| {
+                let _ = writeln!(csv, "{},{},{},{}", step, state.x, state.depth, state.aim);
+                csv
+            },
+        )
+    }
+
+    /// The route seen from the side as a polyline, depth growing downwards. The view box spans
+    /// every state, negative coordinates included
+    pub fn to_svg(&self) -> String {
+        let bounds = |coordinate: fn(&State) -> Position| {
+            let values = self.states.iter().map(coordinate);
+            let min = values.clone().min().unwrap_or_default();
+            (min, (values.max().unwrap_or_default() - min).max(1))
+        };
+        let (min_x, width) = bounds(|s| s.x);
+        let (min_depth, height) = bounds(|s| s.depth);
+        let points = self
+            .states
+            .iter()
+            .map(|state| format!("{},{}", state.x, state.depth))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\">\n\
+             \x20 <polyline points=\"{}\" fill=\"none\" stroke=\"navy\" stroke-width=\"2\" \
+             vector-effect=\"non-scaling-stroke\"/>\n\
+             </svg>\n",
+            SVG_SIZE.0, SVG_SIZE.1, min_x, min_depth, width, height, points
+        )
+    }
+
+    /// Writes `NAME.csv` and `NAME.svg` in `dir`
+    pub fn export(&self, dir: &Path, name: &str) -> Result<(), String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        for (extension, content) in [("csv", self.to_csv()), ("svg", self.to_svg())] {
+            let path = dir.join(format!("{}.{}", name, extension));
+            std::fs::write(&path, content)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::Script;
+    use crate::submarine::{Aim, BreachPolicy, Direct, Navigator, Submarine};
+
+    fn trajectory(navigator: impl Navigator) -> Trajectory {
+        let script = Script::parse(include_str!("../test.txt")).unwrap();
+        let mut submarine = Submarine::new(navigator, BreachPolicy::Clamp);
+        let mut trajectory = Trajectory::new();
+        for (line, command) in script.commands() {
+            trajectory.record(submarine.execute(line, command).unwrap());
+        }
+        trajectory
+    }
+
+    #[test]
+    fn test_exports() {
+        for (name, trajectory) in [("direct", trajectory(Direct)), ("aim", trajectory(Aim))] {
+            insta::assert_snapshot!(format!("{}_csv", name), trajectory.to_csv());
+            insta::assert_snapshot!(format!("{}_svg", name), trajectory.to_svg());
+        }
+    }
+
+    #[test]
+    fn test_above_surface() {
+        let script = Script::parse("back 2\nup 3\nforward 6\ndown 5").unwrap();
+        let mut submarine = Submarine::new(Direct, BreachPolicy::Allow);
+        let mut trajectory = Trajectory::new();
+        for (line, command) in script.commands() {
+            trajectory.record(submarine.execute(line, command).unwrap());
+        }
+        insta::assert_snapshot!(trajectory.to_svg());
+    }
+}
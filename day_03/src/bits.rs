@@ -0,0 +1,161 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Mul;
+
+const WORD: usize = u64::BITS as usize;
+/// Largest power of 10 fitting in a word, the decimal digits being printed by chunks of 19
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+
+/// Unsigned integer, or set of indices, of any width, stored as little endian 64 bits words
+#[derive(Debug, Clone, Default)]
+pub struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD)],
+        }
+    }
+
+    /// The `len` lowest bits set
+    pub fn ones(len: usize) -> Self {
+        let mut bits = Self::zeros(len);
+        for (i, word) in bits.words.iter_mut().enumerate() {
+            let set = (len - i * WORD).min(WORD);
+            *word = u64::MAX >> (WORD - set);
+        }
+        bits
+    }
+
+    pub fn set(&mut self, index: usize) {
+        if self.words.len() <= index / WORD {
+            self.words.resize(index / WORD + 1, 0);
+        }
+        self.words[index / WORD] |= 1 << (index % WORD);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Lowest set bit
+    pub fn first_one(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .position(|word| *word != 0)
+            .map(|i| i * WORD + self.words[i].trailing_zeros() as usize)
+    }
+
+    /// Keeps the bits also set in `other`, or only those cleared in it when `inverted`
+    pub fn intersection(&self, other: &Self, inverted: bool) -> Self {
+        let words = self.words.iter().enumerate().map(|(i, word)| {
+            let mask = other.words.get(i).copied().unwrap_or_default();
+            word & if inverted { !mask } else { mask }
+        });
+        Self {
+            words: words.collect(),
+        }
+    }
+
+    /// Divides in place, returning the remainder
+    #[allow(clippy::cast_possible_truncation)]
+    fn div_rem(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0;
+        for word in self.words.iter_mut().rev() {
+            let dividend = u128::from(remainder) << WORD | u128::from(*word);
+            *word = (dividend / u128::from(divisor)) as u64;
+            remainder = (dividend % u128::from(divisor)) as u64;
+        }
+        remainder
+    }
+}
+
+/// Equal values, whatever their width
+impl PartialEq for Bits {
+    fn eq(&self, other: &Self) -> bool {
+        let (short, long) = if self.words.len() <= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let (low, high) = long.words.split_at(short.words.len());
+        low == short.words && high.iter().all(|word| *word == 0)
+    }
+}
+
+impl Eq for Bits {}
+
+/// Schoolbook multiplication, the product being as wide as needed
+impl Mul for &Bits {
+    type Output = Bits;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn mul(self, other: Self) -> Bits {
+        let mut words = vec![0; self.words.len() + other.words.len()];
+        for (i, lhs) in self.words.iter().enumerate() {
+            let mut carry = 0;
+            for (j, rhs) in other.words.iter().enumerate() {
+                let product = u128::from(*lhs) * u128::from(*rhs)
+                    + u128::from(words[i + j])
+                    + u128::from(carry);
+                words[i + j] = product as u64;
+                carry = (product >> WORD) as u64;
+            }
+            words[i + other.words.len()] = carry;
+        }
+        Bits { words }
+    }
+}
+
+/// Decimal value
+impl Display for Bits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut value = self.clone();
+        let mut chunks = vec![value.div_rem(DECIMAL_CHUNK)];
+        while !value.is_zero() {
+            chunks.push(value.div_rem(DECIMAL_CHUNK));
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        chunks.try_for_each(|chunk| write!(f, "{:019}", chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_values() {
+        let mut value = Bits::zeros(130);
+        value.set(0);
+        value.set(129);
+        assert_eq!(value.to_string(), "680564733841876926926749214863536422913");
+        assert_eq!(value.count_ones(), 2);
+        let max = Bits::ones(128);
+        assert_eq!(max.to_string(), u128::MAX.to_string());
+        let square = &max * &max;
+        assert_eq!(
+            square.to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+        );
+        assert_eq!(Bits::zeros(3).to_string(), "0");
+        assert_eq!(Bits::zeros(3), Bits::zeros(130));
+        assert_ne!(value, Bits::ones(1));
+        // The product of one word values has a zero high word
+        let mut nine = Bits::zeros(4);
+        nine.set(0);
+        nine.set(3);
+        assert_eq!(&Bits::ones(2) * &Bits::ones(2), nine);
+        assert_eq!(value.intersection(&max, false).first_one(), Some(0));
+        assert_eq!(value.intersection(&max, true).first_one(), Some(129));
+    }
+}
@@ -1,3 +1,8 @@
+mod bits;
+
+use bits::Bits;
+use std::str::FromStr;
+
 const FILE_PATH: &str = "input.txt";
 
 struct BitDistribution {
@@ -6,99 +11,109 @@ struct BitDistribution {
 }
 
 impl BitDistribution {
-    pub const fn max_bit(&self) -> char {
-        if self.count_1 >= self.count_0 {
-            '1'
-        } else {
-            '0'
-        }
+    pub const fn max_bit(&self) -> bool {
+        self.count_1 >= self.count_0
     }
 
-    pub const fn min_bit(&self) -> char {
-        if self.count_1 < self.count_0 {
-            '1'
-        } else {
-            '0'
-        }
+    pub const fn min_bit(&self) -> bool {
+        self.count_1 < self.count_0
     }
 
-    pub fn at(index: usize, bits: &[Vec<char>]) -> Self {
-        let len = bits.len();
-        let count_1 = bits
-            .iter()
-            .filter_map(|arr| arr.get(index).copied())
-            .filter(|c| *c == '1')
-            .count();
+    /// Distribution of a column among the candidate reports
+    pub fn at(column: &Bits, candidates: &Bits) -> Self {
+        let count_1 = candidates.intersection(column, false).count_ones();
         Self {
             count_1,
-            count_0: len - count_1,
+            count_0: candidates.count_ones() - count_1,
         }
     }
+}
 
-    pub fn bit_str(distributions: &[Self], func: impl Fn(&Self) -> char) -> String {
-        distributions.iter().map(func).collect()
-    }
+/// Reports of any width, stored column by column: the column `i` is the set of the reports whose
+/// `i`th bit (from the left) is 1
+struct Diagnostics {
+    reports: Vec<Bits>,
+    columns: Vec<Bits>,
+}
+
+impl FromStr for Diagnostics {
+    type Err = String;
 
-    pub fn bit_vec_value(distributions: &[Self], func: impl Fn(&Self) -> char) -> u32 {
-        let bit_str = Self::bit_str(distributions, func);
-        bit_str_value(&bit_str)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().filter(|line| !line.is_empty()).collect();
+        let width = lines.first().ok_or("File is empty")?.len();
+        let mut columns = vec![Bits::zeros(lines.len()); width];
+        let mut reports = vec![];
+        for (index, line) in lines.into_iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Report {}: expected {} bits", line, width));
+            }
+            let mut report = Bits::zeros(width);
+            for (column, bit) in line.chars().enumerate() {
+                match bit {
+                    '0' => (),
+                    '1' => {
+                        report.set(width - 1 - column);
+                        columns[column].set(index);
+                    }
+                    _ => return Err(format!("Report {}: wrong bit `{}`", line, bit)),
+                }
+            }
+            reports.push(report);
+        }
+        Ok(Self { reports, columns })
     }
 }
 
-fn bit_str_value(bit_str: &str) -> u32 {
-    u32::from_str_radix(bit_str, 2).unwrap()
+impl Diagnostics {
+    const fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Value whose bits are picked from the distribution of each column
+    fn bit_vec_value(&self, func: impl Fn(&BitDistribution) -> bool) -> Bits {
+        let all = Bits::ones(self.reports.len());
+        let mut value = Bits::zeros(self.width());
+        for (i, column) in self.columns.iter().enumerate() {
+            if func(&BitDistribution::at(column, &all)) {
+                value.set(self.width() - 1 - i);
+            }
+        }
+        value
+    }
+
+    /// Filters the reports column by column, keeping those whose bit is picked from the
+    /// distribution of the remaining ones
+    fn rating(&self, func: impl Fn(&BitDistribution) -> bool) -> &Bits {
+        let mut candidates = Bits::ones(self.reports.len());
+        for column in &self.columns {
+            if candidates.count_ones() <= 1 {
+                break;
+            }
+            let target_bit = func(&BitDistribution::at(column, &candidates));
+            let new_candidates = candidates.intersection(column, !target_bit);
+            if !new_candidates.is_zero() {
+                candidates = new_candidates;
+            }
+        }
+        &self.reports[candidates.first_one().unwrap()]
+    }
 }
 
-fn day_1(bits: &[Vec<char>], expected_len: usize) {
-    let distributions: Vec<BitDistribution> = (0..expected_len)
-        .map(|i| BitDistribution::at(i, bits))
-        .collect();
-    let gamma = BitDistribution::bit_vec_value(&distributions, BitDistribution::max_bit);
-    let epsilon = BitDistribution::bit_vec_value(&distributions, BitDistribution::min_bit);
+fn day_1(diagnostics: &Diagnostics) {
+    let gamma = diagnostics.bit_vec_value(BitDistribution::max_bit);
+    let epsilon = diagnostics.bit_vec_value(BitDistribution::min_bit);
     println!(
         "Part1. Gamma = {}, Epsilon = {}. Result = {}",
         gamma,
         epsilon,
-        gamma * epsilon
+        &gamma * &epsilon
     );
 }
 
-fn day2_candidate(
-    previous_candidate: &[Vec<char>],
-    func: impl Fn(&BitDistribution) -> char,
-    index: usize,
-) -> Option<Vec<Vec<char>>> {
-    if previous_candidate.len() <= 1 {
-        return None;
-    }
-    let target_bit = func(&BitDistribution::at(index, previous_candidate));
-    let new_candidate: Vec<Vec<char>> = previous_candidate
-        .iter()
-        .cloned()
-        .filter(|arr| arr.get(index).map_or(false, |bit| *bit == target_bit))
-        .collect();
-    if new_candidate.is_empty() {
-        None
-    } else {
-        Some(new_candidate)
-    }
-}
-
-fn day_2(bits: &[Vec<char>], max_len: usize) {
-    let (oxygen, co2) = (0..max_len).fold(
-        (bits.to_vec(), bits.to_vec()),
-        |(mut oxy_candidates, mut co2_candidates), i| {
-            if let Some(candidate) = day2_candidate(&oxy_candidates, BitDistribution::max_bit, i) {
-                oxy_candidates = candidate;
-            }
-            if let Some(candidate) = day2_candidate(&co2_candidates, BitDistribution::min_bit, i) {
-                co2_candidates = candidate;
-            }
-            (oxy_candidates, co2_candidates)
-        },
-    );
-    let oxygen = bit_str_value(oxygen.first().unwrap().iter().collect::<String>().as_str());
-    let co2 = bit_str_value(co2.first().unwrap().iter().collect::<String>().as_str());
+fn day_2(diagnostics: &Diagnostics) {
+    let oxygen = diagnostics.rating(BitDistribution::max_bit);
+    let co2 = diagnostics.rating(BitDistribution::min_bit);
     println!(
         "Part2. Oxygen = {}, CO2 = {}. Result = {}",
         oxygen,
@@ -108,12 +123,40 @@ fn day_2(bits: &[Vec<char>], max_len: usize) {
 }
 
 fn main() {
-    let bits: Vec<Vec<char>> = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .split('\n')
-        .map(|str| str.chars().collect())
-        .collect();
-    let expected_len = bits.first().expect("File is empty").len();
-    day_1(&bits, expected_len);
-    day_2(&bits, expected_len);
+    let diagnostics: Diagnostics = aoc_common::input!(FILE_PATH).unwrap().parse().unwrap();
+    day_1(&diagnostics);
+    day_2(&diagnostics);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratings() {
+        let diagnostics: Diagnostics = include_str!("../test.txt").parse().unwrap();
+        let gamma = diagnostics.bit_vec_value(BitDistribution::max_bit);
+        assert_eq!(gamma.to_string(), "22");
+        assert_eq!(
+            diagnostics.rating(BitDistribution::min_bit).to_string(),
+            "10"
+        );
+        // Reports wider than a word, the low bits deciding the ratings
+        let wide: Diagnostics = "1000000000000000000000000000000000000000000000000000000000000000000011\n\
+                                 1000000000000000000000000000000000000000000000000000000000000000000010\n\
+                                 1000000000000000000000000000000000000000000000000000000000000000000000"
+            .parse()
+            .unwrap();
+        assert_eq!(wide.width(), 70);
+        let oxygen = wide.rating(BitDistribution::max_bit);
+        assert_eq!(oxygen.to_string(), "590295810358705651715");
+        assert_eq!(
+            wide.rating(BitDistribution::min_bit).to_string(),
+            "590295810358705651712"
+        );
+        assert_eq!(
+            "101\n11".parse::<Diagnostics>().err(),
+            Some(String::from("Report 11: expected 3 bits"))
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use aoc_common::explain;
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -130,6 +131,12 @@ fn find_winning_board(results: &BingoResults, mut boards: Vec<Board>) -> Option<
     for result in &results.0 {
         for (board_id, board) in boards.iter_mut().enumerate() {
             if let Some(sum) = board.handle_marked_number(*result) {
+                explain!(
+                    "board_won",
+                    board = board_id,
+                    draw = result,
+                    unmarked_sum = sum
+                );
                 return Some((board_id, *result * sum));
             }
         }
@@ -149,6 +156,12 @@ fn find_loosing_board(results: &BingoResults, boards: Vec<Board>) -> Option<(usi
                 res
             });
         for (id, sum) in winning_boards {
+            explain!(
+                "board_won",
+                board = id,
+                draw = result,
+                boards_left = current_boards.len() - 1
+            );
             if current_boards.len() == 1 {
                 return Some((id, sum));
             }
@@ -159,7 +172,7 @@ fn find_loosing_board(results: &BingoResults, boards: Vec<Board>) -> Option<(usi
 }
 
 fn main() {
-    let mut contents: Vec<String> = std::fs::read_to_string(FILE_PATH)
+    let mut contents: Vec<String> = aoc_common::input!(FILE_PATH)
         .unwrap()
         .split("\n\n")
         .map(ToString::to_string)
@@ -167,8 +180,7 @@ fn main() {
     let bingo_results = BingoResults::from_str(&contents.remove(0)).unwrap();
     let boards: Vec<Board> = contents
         .iter()
-        .cloned()
-        .map(|str| Board::from_str(&str).unwrap())
+        .map(|str| Board::from_str(str).unwrap())
         .collect();
     let (winner_id, winner_result) = find_winning_board(&bingo_results, boards.clone()).unwrap();
     println!("Part 1: id = {} result = {}", winner_id, winner_result);
@@ -85,11 +85,9 @@ fn get_result(max_point: IVec2, lines: &[Line], filter: impl Fn(&Line, IVec2) ->
 }
 
 fn main() {
-    let lines: Vec<Line> = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .split('\n')
-        .map(|s| s.parse().unwrap())
-        .collect();
+    let lines: Vec<Line> = aoc_common::input::records(aoc_common::reader!(FILE_PATH).unwrap())
+        .collect::<Result<_, _>>()
+        .unwrap();
     let max_point = lines.iter().fold(IVec2::default(), |acc, line| {
         let x_max = max(line.start.x, line.end.x);
         let y_max = max(line.start.y, line.end.y);
@@ -0,0 +1,35 @@
+//! Lanternfish population growth, usable without `std`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use aoc_common::arith;
+use aoc_common::simulation::Simulation;
+
+#[cfg(feature = "wide")]
+pub type Count = u128;
+#[cfg(not(feature = "wide"))]
+pub type Count = usize;
+
+/// Lanternfish counts per timer value
+pub struct School(pub [Count; 9]);
+
+impl Simulation for School {
+    type State = [Count; 9];
+
+    fn step(&mut self) {
+        self.0.rotate_left(1);
+        self.0[6] = arith!(Add, self.0[6], self.0[8]);
+    }
+
+    fn state(&self) -> Self::State {
+        self.0
+    }
+}
+
+impl School {
+    #[must_use]
+    pub fn population(&self) -> Count {
+        self.0
+            .iter()
+            .fold(0, |total, count| arith!(Add, total, *count))
+    }
+}
@@ -11,7 +11,7 @@ fn find_best_cost(positions: &[i32], cost_fn: impl Fn(i32) -> i32) -> Option<i32
 }
 
 fn main() {
-    let positions: Vec<i32> = std::fs::read_to_string(FILE_PATH)
+    let positions: Vec<i32> = aoc_common::input!(FILE_PATH)
         .unwrap()
         .split(',')
         .map(|s| s.parse().unwrap())
@@ -62,10 +62,7 @@ impl Entry {
                                 to.iter()
                                     .copied()
                                     .filter(|c| !done_values.contains(c))
-                                    .map(|c| {
-                                        vec.push(c);
-                                        c
-                                    })
+                                    .inspect(|c| vec.push(*c))
                                     .collect()
                             });
                             *entry = entry.iter().filter(|c| to.contains(c)).copied().collect();
@@ -140,7 +137,7 @@ fn part2(entries: &[Entry]) -> usize {
 }
 
 fn main() {
-    let entries: Vec<Entry> = std::fs::read_to_string(FILE_PATH)
+    let entries: Vec<Entry> = aoc_common::input!(FILE_PATH)
         .unwrap()
         .lines()
         .map(|s| Entry::from_str(s).unwrap())
@@ -23,7 +23,7 @@ impl DigitPattern {
     }
 
     fn contained_in_str(pattern: &[char], str: &str) -> bool {
-        pattern.iter().fold(true, |res, c| str.contains(*c) && res)
+        pattern.iter().all(|c| str.contains(*c))
     }
 
     fn identify_pattern_of_5(&self, pattern: &str) -> usize {
@@ -136,7 +136,7 @@ fn part2(entries: &[Entry]) -> usize {
 }
 
 fn main() {
-    let entries: Vec<Entry> = std::fs::read_to_string(FILE_PATH)
+    let entries: Vec<Entry> = aoc_common::input!(FILE_PATH)
         .unwrap()
         .lines()
         .map(|s| Entry::from_str(s).unwrap())
@@ -89,7 +89,7 @@ impl HeightMap {
 }
 
 fn main() {
-    let map = HeightMap::from_str(std::fs::read_to_string(FILE_PATH).unwrap().as_str()).unwrap();
+    let map = HeightMap::from_str(aoc_common::input!(FILE_PATH).unwrap().as_str()).unwrap();
     println!(
         "Part1: risk level = {}",
         map.low_points()
@@ -0,0 +1,56 @@
+//! Syntax checking of navigation subsystem lines, usable without `std`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns the closing chars completing `line`, in reverse order
+///
+/// # Errors
+///
+/// Fails with the first illegal closing char
+///
+/// # Panics
+///
+/// Panics on chars which aren't brackets
+pub fn handle_line(mut line: impl Iterator<Item = char>) -> Result<Vec<char>, char> {
+    line.try_fold(vec![], |mut expected_chars, c| {
+        match c {
+            '<' => expected_chars.push('>'),
+            '(' => expected_chars.push(')'),
+            '[' => expected_chars.push(']'),
+            '{' => expected_chars.push('}'),
+            '>' | ')' | ']' | '}' => {
+                if expected_chars.pop() != Some(c) {
+                    return Err(c);
+                }
+            }
+            _ => panic!("Unhandled char `{}`", c),
+        }
+        Ok(expected_chars)
+    })
+}
+
+#[must_use]
+pub const fn part1_score(c: char) -> u32 {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1_197,
+        '>' => 25_137,
+        _ => 0,
+    }
+}
+
+#[must_use]
+pub const fn part2_score(c: char) -> u64 {
+    match c {
+        ')' => 1,
+        ']' => 2,
+        '}' => 3,
+        '>' => 4,
+        _ => 0,
+    }
+}
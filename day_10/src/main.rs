@@ -1,56 +1,37 @@
-const FILE_PATH: &str = "input.txt";
-
-fn handle_line(mut line: impl Iterator<Item = char>) -> Result<Vec<char>, char> {
-    line.try_fold(vec![], |mut expected_chars, c| {
-        match c {
-            '<' => expected_chars.push('>'),
-            '(' => expected_chars.push(')'),
-            '[' => expected_chars.push(']'),
-            '{' => expected_chars.push('}'),
-            '>' | ')' | ']' | '}' => {
-                if !expected_chars.pop().map_or(false, |ec| ec == c) {
-                    return Err(c);
-                }
-            }
-            _ => panic!("Unhandled char `{}`", c),
-        }
-        Ok(expected_chars)
-    })
-}
+use aoc_common::explain;
+use aoc_common::input::records;
+use day_10::{handle_line, part1_score, part2_score};
 
-const fn part1_score(c: char) -> u32 {
-    match c {
-        ')' => 3,
-        ']' => 57,
-        '}' => 1_197,
-        '>' => 25_137,
-        _ => 0,
-    }
-}
-
-const fn part2_score(c: char) -> u64 {
-    match c {
-        ')' => 1,
-        ']' => 2,
-        '}' => 3,
-        '>' => 4,
-        _ => 0,
-    }
-}
+const FILE_PATH: &str = "input.txt";
 
 fn main() {
-    let (score_a, mut score_b) = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .lines()
-        .map(|l| handle_line(l.chars()))
-        .fold((0_u32, vec![]), |(mut score_a, mut score_b), r| {
+    let (score_a, mut score_b) = records::<String>(aoc_common::reader!(FILE_PATH).unwrap())
+        .map(|l| handle_line(l.unwrap().chars()))
+        .enumerate()
+        .fold((0_u32, vec![]), |(mut score_a, mut score_b), (i, r)| {
             match r {
-                Ok(v) => score_b.push(
-                    v.iter()
+                Ok(v) => {
+                    let score = v
+                        .iter()
                         .rev()
-                        .fold(0_u64, |res, c| res * 5 + part2_score(*c)),
-                ),
-                Err(e) => score_a += part1_score(e),
+                        .fold(0_u64, |res, c| res * 5 + part2_score(*c));
+                    explain!(
+                        "incomplete_line",
+                        line = i + 1,
+                        completion = v.iter().rev().collect::<String>(),
+                        score = score
+                    );
+                    score_b.push(score);
+                }
+                Err(e) => {
+                    explain!(
+                        "corrupted_line",
+                        line = i + 1,
+                        found = e,
+                        score = part1_score(e)
+                    );
+                    score_a += part1_score(e);
+                }
             }
             (score_a, score_b)
         });
@@ -1,3 +1,6 @@
+use aoc_common::repl::{self, Session};
+use aoc_common::simulation::Simulation;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 const FILE_PATH: &str = "input.txt";
@@ -55,7 +58,7 @@ impl SquidGrid {
             .filter_map(|(dx, dy)| {
                 let x = (x as isize) + *dx as isize;
                 let y = (y as isize) + *dy as isize;
-                (x >= 0 && y >= 0).then(|| (x as usize, y as usize))
+                (x >= 0 && y >= 0).then_some((x as usize, y as usize))
             })
             .collect()
     }
@@ -77,7 +80,17 @@ impl SquidGrid {
         }
     }
 
-    fn handle_step(&mut self) {
+    fn is_synced(&self) -> bool {
+        self.energy_levels
+            .iter()
+            .all(|l| l.iter().all(|level| *level == 0))
+    }
+}
+
+impl Simulation for SquidGrid {
+    type State = [[u8; 10]; 10];
+
+    fn step(&mut self) {
         self.flashed.clear();
         for y in 0..10 {
             for x in 0..10 {
@@ -86,23 +99,86 @@ impl SquidGrid {
         }
     }
 
-    fn is_synced(&self) -> bool {
+    fn state(&self) -> Self::State {
         self.energy_levels
+    }
+}
+
+impl Display for SquidGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let buff: Vec<String> = self
+            .energy_levels
             .iter()
-            .all(|l| l.iter().all(|level| *level == 0))
+            .map(|l| l.iter().map(ToString::to_string).collect())
+            .collect();
+        write!(f, "{}", buff.join("\n"))
+    }
+}
+
+impl Session for SquidGrid {
+    fn help(&self) -> &'static str {
+        "step [STEPS]\n    Advance the octopuses, one step by default\n\
+         show\n    Print the energy levels\n\
+         sync\n    Step until every octopus flashes at once"
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "step" => {
+                let steps = if args.is_empty() {
+                    1
+                } else {
+                    repl::arg(args, 0, "STEPS")?
+                };
+                self.run(steps);
+                Ok(format!(
+                    "{} flashed during the last step, {} in total",
+                    self.flashed.len(),
+                    self.flashes_count
+                ))
+            }
+            "show" => Ok(self.to_string()),
+            "sync" => Ok(format!(
+                "Synced after {} more steps",
+                self.run_until(Self::is_synced)
+            )),
+            _ => Err(format!("Unknown command `{}`", command)),
+        }
     }
 }
 
 fn main() {
-    let mut grid = SquidGrid::from_str(&std::fs::read_to_string(FILE_PATH).unwrap()).unwrap();
-    (0..100).for_each(|_| {
-        grid.handle_step();
-    });
-    println!("Part 1. Total = {}", grid.flashes_count);
-    let mut step = 100;
-    while !grid.is_synced() {
-        grid.handle_step();
-        step += 1;
+    let mut grid = SquidGrid::from_str(&aoc_common::input!(FILE_PATH).unwrap()).unwrap();
+    if repl::requested() {
+        repl::run("day_11", &mut grid).unwrap();
+        return;
     }
+    grid.run(100);
+    println!("Part 1. Total = {}", grid.flashes_count);
+    let step = 100 + grid.run_until(SquidGrid::is_synced);
     println!("Part 2. Sync at {}", step);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync() {
+        let mut grid = SquidGrid::from_str(include_str!("../test.txt")).unwrap();
+        grid.run(10);
+        assert_eq!(grid.flashes_count, 204);
+        grid.run(90);
+        assert_eq!(grid.flashes_count, 1656);
+        assert_eq!(grid.run_until(SquidGrid::is_synced), 95);
+    }
+
+    #[test]
+    fn test_rendering() {
+        let mut grid = SquidGrid::from_str(include_str!("../test.txt")).unwrap();
+        grid.run(10);
+        insta::assert_snapshot!("step_10", grid.to_string());
+        grid.run_until(SquidGrid::is_synced);
+        insta::assert_snapshot!("synced", grid.to_string());
+    }
+}
@@ -75,7 +75,7 @@ impl CaveSystem {
 }
 
 fn main() {
-    let map = CaveSystem::from_str(&std::fs::read_to_string(FILE_PATH).unwrap()).unwrap();
+    let map = CaveSystem::from_str(&aoc_common::input!(FILE_PATH).unwrap()).unwrap();
     println!("Part1. Path count = {}", map.path_count(false));
     println!("Part2. Path count = {}", map.path_count(true));
 }
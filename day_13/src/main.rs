@@ -5,6 +5,7 @@ use std::str::FromStr;
 const FILE_PATH: &str = "input.txt";
 
 type Coords = (u32, u32);
+type FoldClosure = Box<dyn Fn(&Coords) -> Option<(Coords, Coords)>>;
 
 #[derive(Debug, Copy, Clone)]
 enum FoldAlong {
@@ -28,13 +29,13 @@ impl FromStr for FoldAlong {
         match text.to_ascii_lowercase().as_str() {
             "fold along y" => Ok(Self::Y(value)),
             "fold along x" => Ok(Self::X(value)),
-            _ => return Err(format!("Wrong fold instruction `{}`", text)),
+            _ => Err(format!("Wrong fold instruction `{}`", text)),
         }
     }
 }
 
 impl FoldAlong {
-    fn coords_to_fold_closure(self) -> Box<dyn Fn(&Coords) -> Option<(Coords, Coords)>> {
+    fn coords_to_fold_closure(self) -> FoldClosure {
         match self {
             Self::X(threshold) => Box::new(move |(x, y): &Coords| {
                 x.checked_sub(threshold)
@@ -99,7 +100,7 @@ impl Display for Positions {
 }
 
 fn main() {
-    let (mut positions, fold_instructions) = std::fs::read_to_string(FILE_PATH)
+    let (mut positions, fold_instructions) = aoc_common::input!(FILE_PATH)
         .unwrap()
         .split_once("\n\n")
         .map(|(p, f)| {
@@ -119,3 +120,27 @@ fn main() {
     }
     println!("Part 2: \n{}", positions);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fold_all(input: &str) -> Positions {
+        let (positions, instructions) = input.split_once("\n\n").unwrap();
+        let mut positions = Positions::from_str(positions).unwrap();
+        for instruction in instructions.lines() {
+            positions.fold(FoldAlong::from_str(instruction).unwrap());
+        }
+        positions
+    }
+
+    #[test]
+    fn test_example_rendering() {
+        insta::assert_snapshot!(fold_all(include_str!("../test.txt")));
+    }
+
+    #[test]
+    fn test_code_rendering() {
+        insta::assert_snapshot!(fold_all(include_str!("../input.txt")));
+    }
+}
@@ -1,19 +1,31 @@
+use aoc_common::arith;
+use aoc_common::simulation::Simulation;
 use itertools::{Itertools, MinMaxResult};
 use std::collections::HashMap;
 use std::str::FromStr;
 
 const FILE_PATH: &str = "input.txt";
 type Pair = [char; 2];
+#[cfg(feature = "wide")]
+type Count = u128;
+#[cfg(not(feature = "wide"))]
+type Count = usize;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct Polymer {
-    pub pairs: HashMap<Pair, usize>,
-    pub counts: HashMap<char, usize>,
+    pub pairs: HashMap<Pair, Count>,
+    pub counts: HashMap<char, Count>,
 }
 
 #[derive(Debug)]
 struct PairInsertions(HashMap<Pair, char>);
 
+#[derive(Debug)]
+struct Polymerization {
+    polymer: Polymer,
+    insertions: PairInsertions,
+}
+
 impl FromStr for Polymer {
     type Err = String;
 
@@ -65,9 +77,11 @@ impl PairInsertions {
             },
             |mut poly, (k, v)| {
                 if let Some(insertion) = self.0.get(&k) {
-                    *poly.counts.entry(*insertion).or_insert(0) += v;
+                    let count = poly.counts.entry(*insertion).or_insert(0);
+                    *count = arith!(Add, *count, v);
                     for pair in [[k[0], *insertion], [*insertion, k[1]]] {
-                        *poly.pairs.entry(pair).or_insert(0) += v;
+                        let count = poly.pairs.entry(pair).or_insert(0);
+                        *count = arith!(Add, *count, v);
                     }
                 }
                 poly
@@ -77,7 +91,7 @@ impl PairInsertions {
 }
 
 impl Polymer {
-    fn subtracted_repartition(&self) -> usize {
+    fn subtracted_repartition(&self) -> Count {
         match self.counts.values().minmax() {
             MinMaxResult::NoElements | MinMaxResult::OneElement(_) => 0,
             MinMaxResult::MinMax(min, max) => max.saturating_sub(*min),
@@ -85,8 +99,26 @@ impl Polymer {
     }
 }
 
+impl Simulation for Polymerization {
+    type State = Vec<(Pair, Count)>;
+
+    fn step(&mut self) {
+        let polymer = std::mem::take(&mut self.polymer);
+        self.polymer = self.insertions.apply_to_polymer(polymer);
+    }
+
+    fn state(&self) -> Self::State {
+        self.polymer
+            .pairs
+            .iter()
+            .map(|(pair, count)| (*pair, *count))
+            .sorted()
+            .collect()
+    }
+}
+
 fn main() {
-    let (mut polymer, pairs): (Polymer, PairInsertions) = std::fs::read_to_string(FILE_PATH)
+    let (polymer, insertions): (Polymer, PairInsertions) = aoc_common::input!(FILE_PATH)
         .unwrap()
         .split_once("\n\n")
         .map(|(poly, pairs)| {
@@ -96,11 +128,18 @@ fn main() {
             )
         })
         .unwrap();
-    for i in 1..=40 {
-        polymer = pairs.apply_to_polymer(polymer);
-        if i == 10 {
-            println!("Part 1: {}", polymer.subtracted_repartition());
-        }
-    }
-    println!("Part 2: {}", polymer.subtracted_repartition());
+    let mut polymerization = Polymerization {
+        polymer,
+        insertions,
+    };
+    polymerization.run(10);
+    println!(
+        "Part 1: {}",
+        polymerization.polymer.subtracted_repartition()
+    );
+    polymerization.run(30);
+    println!(
+        "Part 2: {}",
+        polymerization.polymer.subtracted_repartition()
+    );
 }
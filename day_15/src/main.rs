@@ -111,7 +111,7 @@ fn incremented_vec(vec: &[u8], delta: u8) -> Vec<u8> {
 }
 
 fn main() {
-    let map = Map::from_str(std::fs::read_to_string(FILE_PATH).unwrap().as_str()).unwrap();
+    let map = Map::from_str(aoc_common::input!(FILE_PATH).unwrap().as_str()).unwrap();
     println!("Part 1: {}", map.find_cheapest_path().unwrap());
 
     let mut prev = map.map;
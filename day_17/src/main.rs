@@ -84,7 +84,7 @@ fn compute_velocities(bounds: &Bounds) -> (usize, Option<i32>) {
 }
 
 fn main() {
-    let bounds = Bounds::from_str(std::fs::read_to_string(FILE_PATH).unwrap().as_str()).unwrap();
+    let bounds = Bounds::from_str(aoc_common::input!(FILE_PATH).unwrap().as_str()).unwrap();
     let (velocities, max_y) = compute_velocities(&bounds);
     println!("Part 1: Max Y pos is {}", max_y.unwrap());
     println!("Part 2: There are {} valid velocities", velocities);
@@ -1,3 +1,4 @@
+use aoc_common::explain;
 use glam::IVec3;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
@@ -139,7 +140,7 @@ impl ScannerData {
 }
 
 fn main() {
-    let mut scanners: Vec<ScannerData> = std::fs::read_to_string(FILE_PATH)
+    let mut scanners: Vec<ScannerData> = aoc_common::input!(FILE_PATH)
         .unwrap()
         .split("\n\n")
         .map(|s| ScannerData::from_str(s).unwrap())
@@ -151,7 +152,16 @@ fn main() {
     let mut positions = vec![IVec3::ZERO];
     while handled.len() < expected_len {
         for (i, scanner) in scanners.iter().enumerate() {
-            if let Some(mut matched) = handled.iter().find_map(|other| scanner.find_match(other)) {
+            if let Some((other_id, mut matched)) = handled
+                .iter()
+                .find_map(|other| Some((other.id, scanner.find_match(other)?)))
+            {
+                explain!(
+                    "scanner_matched",
+                    scanner = scanner.id,
+                    with_scanner = other_id,
+                    position = matched.delta
+                );
                 matched.rotated.translate(matched.delta);
                 position_set.extend(matched.rotated.beacons.iter().copied());
                 scanners.remove(i);
@@ -177,3 +187,17 @@ fn main() {
         .unwrap();
     println!("Part2: Max distance = {}", max_distance);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanners_rendering() {
+        let scanners: Vec<String> = include_str!("../test.txt")
+            .split("\n\n")
+            .map(|s| ScannerData::from_str(s).unwrap().to_string())
+            .collect();
+        insta::assert_snapshot!(scanners.join("\n"));
+    }
+}
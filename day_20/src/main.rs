@@ -1,4 +1,5 @@
 #![allow(clippy::cast_possible_wrap)]
+use aoc_common::simulation::Simulation;
 use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
@@ -21,6 +22,15 @@ const NEIGHBOR_COORDS: [Pixel; 9] = [
 #[derive(Debug, Clone)]
 struct Image(HashSet<Pixel>);
 
+/// Successive applications of the enhancement algorithm to an image
+#[derive(Debug)]
+struct Enhancement {
+    algorithm: [bool; 512],
+    image: Image,
+    /// Whether the next computation stores unlit pixels instead of lit ones
+    inverted: bool,
+}
+
 impl FromStr for Image {
     type Err = String;
 
@@ -31,7 +41,7 @@ impl FromStr for Image {
             .flat_map(|(y, l)| {
                 l.chars()
                     .enumerate()
-                    .filter_map(|(x, c)| (c == '#').then(|| [x as isize, y as isize]))
+                    .filter_map(|(x, c)| (c == '#').then_some([x as isize, y as isize]))
                     .collect::<HashSet<Pixel>>()
             })
             .collect();
@@ -39,6 +49,23 @@ impl FromStr for Image {
     }
 }
 
+impl FromStr for Enhancement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, image) = s.split_once("\n\n").ok_or("Missing image")?;
+        let algorithm: Vec<bool> = algorithm.chars().map(|c| c == '#').collect();
+        let len = algorithm.len();
+        Ok(Self {
+            algorithm: algorithm
+                .try_into()
+                .map_err(|_| format!("Wrong algorithm length {}, expected 512", len))?,
+            image: Image::from_str(image)?,
+            inverted: true,
+        })
+    }
+}
+
 impl Display for Image {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let [x_min, y_min] = [
@@ -52,13 +79,7 @@ impl Display for Image {
         let buff: Vec<String> = (y_min..=y_max)
             .map(|y| {
                 (x_min..=x_max)
-                    .map(|x| {
-                        if self.0.get(&[x, y]).is_some() {
-                            '#'
-                        } else {
-                            '.'
-                        }
-                    })
+                    .map(|x| if self.0.contains(&[x, y]) { '#' } else { '.' })
                     .collect::<String>()
             })
             .collect();
@@ -85,7 +106,7 @@ impl Image {
             .iter()
             .map(|[dx, dy]| {
                 let coord = [x + dx, y + dy];
-                if self.0.get(&coord).is_some() == inverted {
+                if self.0.contains(&coord) == inverted {
                     '1'
                 } else {
                     '0'
@@ -104,7 +125,7 @@ impl Image {
                 (x_min..=x_max)
                     .filter_map(|x| {
                         let coord = [x, y];
-                        (self.pixel_data(coord, algorithm, inverted) != inverted).then(|| coord)
+                        (self.pixel_data(coord, algorithm, inverted) != inverted).then_some(coord)
                     })
                     .collect::<HashSet<Pixel>>()
             })
@@ -113,26 +134,43 @@ impl Image {
     }
 }
 
+impl Simulation for Enhancement {
+    type State = (Vec<Pixel>, bool);
+
+    fn step(&mut self) {
+        self.image = self.image.compute_image(&self.algorithm, self.inverted);
+        self.inverted = !self.inverted;
+    }
+
+    fn state(&self) -> Self::State {
+        let mut pixels: Vec<Pixel> = self.image.0.iter().copied().collect();
+        pixels.sort_unstable();
+        (pixels, self.inverted)
+    }
+}
+
 fn main() {
-    let (algo, mut image) = std::fs::read_to_string(FILE_PATH)
-        .unwrap()
-        .split_once("\n\n")
-        .map(|(algo, input)| {
-            let algo: [bool; 512] = algo
-                .chars()
-                .map(|c| c == '#')
-                .collect::<Vec<bool>>()
-                .try_into()
-                .unwrap();
-            let input = Image::from_str(input).unwrap();
-            (algo, input)
-        })
-        .unwrap();
-    for i in 0..50 {
-        image = image.compute_image(&algo, i % 2 == 0);
-        if i == 1 {
-            println!("Part 1: {} lit pixels", image.0.len());
-        }
+    let mut enhancement: Enhancement = aoc_common::input!(FILE_PATH).unwrap().parse().unwrap();
+    enhancement.run(2);
+    println!("Part 1: {} lit pixels", enhancement.image.0.len());
+    enhancement.run(48);
+    println!("Part 1: {} lit pixels", enhancement.image.0.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_rendering() {
+        let (_, image) = include_str!("../test.txt").split_once("\n\n").unwrap();
+        insta::assert_snapshot!(Image::from_str(image).unwrap());
+    }
+
+    #[test]
+    fn test_enhanced_rendering() {
+        let mut enhancement: Enhancement = include_str!("../input.txt").parse().unwrap();
+        enhancement.run(2);
+        insta::assert_snapshot!(enhancement.image);
     }
-    println!("Part 1: {} lit pixels", image.0.len());
 }
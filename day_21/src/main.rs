@@ -1,8 +1,16 @@
 #![allow(clippy::cast_possible_truncation)]
+use aoc_common::arith;
 use std::collections::HashMap;
+use std::str::FromStr;
 
+const FILE_PATH: &str = "input.txt";
 const BOARD_SIZE: u32 = 10;
 
+#[cfg(feature = "wide")]
+type Universes = u128;
+#[cfg(not(feature = "wide"))]
+type Universes = u64;
+
 #[derive(Debug, Copy, Clone)]
 struct Player {
     score: u32,
@@ -24,6 +32,17 @@ impl Player {
     }
 }
 
+impl FromStr for Player {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.rsplit_once(": ")
+            .and_then(|(_, position)| position.trim().parse().ok())
+            .map(Self::new)
+            .ok_or(format!("Invalid starting position: {}", s))
+    }
+}
+
 #[derive(Debug, Default)]
 struct TestDice {
     value: u32,
@@ -43,8 +62,12 @@ impl TestDice {
 }
 
 fn main() {
-    let p1 = Player::new(7);
-    let p2 = Player::new(1);
+    let players: Vec<Player> = aoc_common::input!(FILE_PATH)
+        .unwrap()
+        .lines()
+        .map(|l| Player::from_str(l).unwrap())
+        .collect();
+    let [p1, p2]: [Player; 2] = players.try_into().unwrap();
     // Part 1
     {
         let mut p1 = p1;
@@ -66,27 +89,27 @@ fn main() {
     }
     // Part 2
     {
-        let possible_throws: HashMap<u32, u64> =
+        let possible_throws: HashMap<u32, Universes> =
             vec![(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)]
                 .into_iter()
                 .collect();
-        let (mut p1_wins, mut p2_wins) = (0_u64, 0_u64);
+        let (mut p1_wins, mut p2_wins): (Universes, Universes) = (0, 0);
         let mut stack = vec![(0, (p1, p2), 1)];
         while let Some((i, (p1, p2), u)) = stack.pop() {
             if p1.score >= 21 {
-                p1_wins += u;
+                p1_wins = arith!(Add, p1_wins, u);
             } else if p2.score >= 21 {
-                p2_wins += u;
+                p2_wins = arith!(Add, p2_wins, u);
             } else {
                 for (throw, count) in &possible_throws {
                     if i % 2 == 0 {
                         let mut player = p1;
                         player.apply_dice_delta(*throw);
-                        stack.push((i + 1, (player, p2), u * count));
+                        stack.push((i + 1, (player, p2), arith!(Mul, u, *count)));
                     } else {
                         let mut player = p2;
                         player.apply_dice_delta(*throw);
-                        stack.push((i + 1, (p1, player), u * count));
+                        stack.push((i + 1, (p1, player), arith!(Mul, u, *count)));
                     }
                 }
             }
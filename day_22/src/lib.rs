@@ -0,0 +1,122 @@
+//! Reactor cuboid volumes, usable without `std`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use glam::IVec3;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Bounds {
+    pub min: IVec3,
+    pub max: IVec3,
+}
+
+#[derive(Debug, Clone)]
+struct Volume {
+    bounds: Bounds,
+    is_on: bool,
+}
+
+impl Bounds {
+    #[must_use]
+    pub fn volume(&self) -> i64 {
+        (i64::from(self.max.x) - i64::from(self.min.x) + 1)
+            * (i64::from(self.max.y) - i64::from(self.min.y) + 1)
+            * (i64::from(self.max.z) - i64::from(self.min.z) + 1)
+    }
+
+    #[must_use]
+    pub fn intersection_with(&self, other: &Self) -> Option<Self> {
+        let (x_min, x_max) = (self.min.x.max(other.min.x), self.max.x.min(other.max.x));
+        let (y_min, y_max) = (self.min.y.max(other.min.y), self.max.y.min(other.max.y));
+        let (z_min, z_max) = (self.min.z.max(other.min.z), self.max.z.min(other.max.z));
+        if x_min <= x_max && y_min <= y_max && z_min <= z_max {
+            Some(Self {
+                min: IVec3::new(x_min, y_min, z_min),
+                max: IVec3::new(x_max, y_max, z_max),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            self.min.x, self.max.x, self.min.y, self.max.y, self.min.z, self.max.z
+        )
+    }
+}
+
+/// Cubes lit by the reboot steps applied so far, fed one step at a time. Each step adds a signed
+/// volume per intersection with the previous ones, so the memory grows with the steps, quadratically
+/// at worst
+#[derive(Debug, Default)]
+pub struct Reactor {
+    volumes: Vec<Volume>,
+    stop_at_50: bool,
+}
+
+impl Reactor {
+    /// Empty reactor, only considering steps within -50..=50 when `stop_at_50` is set
+    #[must_use]
+    pub const fn new(stop_at_50: bool) -> Self {
+        Self {
+            volumes: Vec::new(),
+            stop_at_50,
+        }
+    }
+
+    pub fn apply(&mut self, bounds: &Bounds, state: bool) {
+        if self.stop_at_50
+            && (bounds.min.x < -50
+                || bounds.min.y < -50
+                || bounds.min.z < -50
+                || bounds.max.x > 50
+                || bounds.max.y > 50
+                || bounds.max.z > 50)
+        {
+            return;
+        }
+        let mut add = Vec::new();
+        if state {
+            add.push(Volume {
+                bounds: *bounds,
+                is_on: true,
+            });
+        }
+        add.extend(self.volumes.iter().filter_map(|v| {
+            v.bounds.intersection_with(bounds).map(|bounds| Volume {
+                bounds,
+                is_on: !v.is_on,
+            })
+        }));
+        self.volumes.extend(add);
+    }
+
+    #[must_use]
+    pub fn volume(&self) -> i64 {
+        self.volumes
+            .iter()
+            .map(|v| {
+                let sign = if v.is_on { 1 } else { -1 };
+                sign * v.bounds.volume()
+            })
+            .sum::<i64>()
+    }
+}
+
+/// Volume lit by `operations`, only within -50..=50 when `stop_at_50` is set
+#[must_use]
+pub fn find_volume(operations: &[(Bounds, bool)], stop_at_50: bool) -> i64 {
+    let mut reactor = Reactor::new(stop_at_50);
+    for (bounds, state) in operations {
+        reactor.apply(bounds, *state);
+    }
+    reactor.volume()
+}
@@ -1,3 +1,6 @@
+use aoc_common::input::records;
+use aoc_common::repl::{self, Session};
+use day_22::{find_volume, Bounds, Reactor};
 use glam::IVec3;
 use regex::{Captures, Regex};
 use std::str::FromStr;
@@ -14,113 +17,92 @@ fn get_capture(captures: &Captures, index: usize) -> Result<i32, String> {
         .ok_or_else(|| String::from("Invalid value"))
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Bounds {
-    min: IVec3,
-    max: IVec3,
-}
-
-#[derive(Debug, Clone)]
-struct Volume {
-    bounds: Bounds,
-    is_on: bool,
-}
+/// A reboot step, turning a cuboid on or off
+#[derive(Debug)]
+struct Step(Bounds, bool);
 
-impl Bounds {
-    fn volume(&self) -> i64 {
-        (i64::from(self.max.x) - i64::from(self.min.x) + 1)
-            * (i64::from(self.max.y) - i64::from(self.min.y) + 1)
-            * (i64::from(self.max.z) - i64::from(self.min.z) + 1)
-    }
+impl FromStr for Step {
+    type Err = String;
 
-    fn intersection_with(&self, other: &Self) -> Option<Self> {
-        let (x_min, x_max) = (self.min.x.max(other.min.x), self.max.x.min(other.max.x));
-        let (y_min, y_max) = (self.min.y.max(other.min.y), self.max.y.min(other.max.y));
-        let (z_min, z_max) = (self.min.z.max(other.min.z), self.max.z.min(other.max.z));
-        if x_min <= x_max && y_min <= y_max && z_min <= z_max {
-            Some(Self {
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let captures = REGEX
+            .captures(line)
+            .ok_or_else(|| format!("Invalid step {}", line))?;
+        let operation = matches!(captures.get(1).map(|m| m.as_str()), Some("on"));
+        let x_min = get_capture(&captures, 2)?;
+        let x_max = get_capture(&captures, 3)?;
+        let y_min = get_capture(&captures, 4)?;
+        let y_max = get_capture(&captures, 5)?;
+        let z_min = get_capture(&captures, 6)?;
+        let z_max = get_capture(&captures, 7)?;
+        Ok(Self(
+            Bounds {
                 min: IVec3::new(x_min, y_min, z_min),
                 max: IVec3::new(x_max, y_max, z_max),
-            })
-        } else {
-            None
-        }
+            },
+            operation,
+        ))
     }
 }
 
 #[derive(Debug)]
 struct EngineOperation(Vec<(Bounds, bool)>);
 
-impl FromStr for EngineOperation {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map: Result<Vec<(Bounds, bool)>, String> = s
-            .lines()
-            .map(|line| {
-                let captures = REGEX.captures(line).ok_or(format!("Invalid str {}", s))?;
-                let operation = matches!(captures.get(1).map(|m| m.as_str()), Some("on"));
-                let x_min = get_capture(&captures, 2)?;
-                let x_max = get_capture(&captures, 3)?;
-                let y_min = get_capture(&captures, 4)?;
-                let y_max = get_capture(&captures, 5)?;
-                let z_min = get_capture(&captures, 6)?;
-                let z_max = get_capture(&captures, 7)?;
-                Ok((
-                    Bounds {
-                        min: IVec3::new(x_min, y_min, z_min),
-                        max: IVec3::new(x_max, y_max, z_max),
-                    },
-                    operation,
-                ))
-            })
-            .collect();
-        Ok(Self(map?))
+impl EngineOperation {
+    fn step(&self, args: &[&str], index: usize) -> Result<&(Bounds, bool), String> {
+        let step: usize = repl::arg(args, index, "STEP")?;
+        self.0.get(step).ok_or_else(|| format!("No step {}", step))
     }
 }
 
-fn find_volume(operations: &[(Bounds, bool)], stop_at_50: bool) -> i64 {
-    let mut volumes: Vec<Volume> = Vec::new();
-    for (bounds, state) in operations {
-        if stop_at_50
-            && (bounds.min.x < -50
-                || bounds.min.y < -50
-                || bounds.min.z < -50
-                || bounds.max.x > 50
-                || bounds.max.y > 50
-                || bounds.max.z > 50)
-        {
-            continue;
-        }
-        let mut add = Vec::new();
-        if *state {
-            add.push(Volume {
-                bounds: *bounds,
-                is_on: true,
-            });
+impl Session for EngineOperation {
+    fn help(&self) -> &'static str {
+        "show STEP\n    Print a reboot step\n\
+         query STEP STEP\n    Intersect the cuboids of two steps\n\
+         volume [STEPS]\n    Count the cubes lit after the first steps, all of them by default"
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "show" => {
+                let (bounds, is_on) = self.step(args, 0)?;
+                let state = if *is_on { "on" } else { "off" };
+                Ok(format!("{} {} ({} cubes)", state, bounds, bounds.volume()))
+            }
+            "query" => {
+                let ((a, _), (b, _)) = (self.step(args, 0)?, self.step(args, 1)?);
+                Ok(a.intersection_with(b).map_or_else(
+                    || String::from("Disjoint"),
+                    |bounds| format!("{} ({} cubes)", bounds, bounds.volume()),
+                ))
+            }
+            "volume" => {
+                let steps = if args.is_empty() {
+                    self.0.len()
+                } else {
+                    repl::arg(args, 0, "STEPS")?
+                };
+                let operations = &self.0[..steps.min(self.0.len())];
+                Ok(format!("{} cubes lit", find_volume(operations, false)))
+            }
+            _ => Err(format!("Unknown command `{}`", command)),
         }
-        add.extend(volumes.iter().filter_map(|v| {
-            v.bounds.intersection_with(bounds).map(|bounds| Volume {
-                bounds,
-                is_on: !v.is_on,
-            })
-        }));
-        volumes.extend(add);
     }
-    volumes
-        .iter()
-        .map(|v| {
-            let sign = if v.is_on { 1 } else { -1 };
-            sign * (v.bounds.volume() as i64)
-        })
-        .sum::<i64>()
 }
 
 fn main() {
-    let operations: EngineOperation =
-        EngineOperation::from_str(std::fs::read_to_string(FILE_PATH).unwrap().as_str()).unwrap();
-    let volume = find_volume(&operations.0, true);
-    println!("Part 1 = {}", volume);
-    let volume = find_volume(&operations.0, false);
-    println!("Part 2 = {}", volume);
+    let steps = records::<Step>(aoc_common::reader!(FILE_PATH).unwrap()).map(Result::unwrap);
+    if repl::requested() {
+        let mut operations = EngineOperation(steps.map(|Step(bounds, on)| (bounds, on)).collect());
+        repl::run("day_22", &mut operations).unwrap();
+        return;
+    }
+    // Both parts are computed in the same pass, the reactors keeping their volumes but not the steps
+    let (mut part_1, mut part_2) = (Reactor::new(true), Reactor::new(false));
+    for Step(bounds, on) in steps {
+        part_1.apply(&bounds, on);
+        part_2.apply(&bounds, on);
+    }
+    println!("Part 1 = {}", part_1.volume());
+    println!("Part 2 = {}", part_2.volume());
 }
@@ -0,0 +1,229 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::str::FromStr;
+
+const FILE_PATH: &str = "input.txt";
+const HALLWAY_LEN: usize = 11;
+/// Hallway position in front of each room, amphipods can't stop there
+const ENTRANCES: [usize; 4] = [2, 4, 6, 8];
+/// Rows inserted between the two rows of the folded diagram
+const UNFOLDED_ROWS: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    const fn energy(self) -> usize {
+        match self {
+            Self::Amber => 1,
+            Self::Bronze => 10,
+            Self::Copper => 100,
+            Self::Desert => 1000,
+        }
+    }
+
+    const fn room(self) -> usize {
+        self as usize
+    }
+
+    fn parse(c: char) -> Result<Option<Self>, String> {
+        match c {
+            '.' => Ok(None),
+            'A' => Ok(Some(Self::Amber)),
+            'B' => Ok(Some(Self::Bronze)),
+            'C' => Ok(Some(Self::Copper)),
+            'D' => Ok(Some(Self::Desert)),
+            _ => Err(format!("Invalid cell {}", c)),
+        }
+    }
+}
+
+type Cell = Option<Amphipod>;
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+struct Burrow {
+    hallway: [Cell; HALLWAY_LEN],
+    /// Room cells, from the entrance to the bottom
+    rooms: [Vec<Cell>; 4],
+}
+
+impl FromStr for Burrow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().skip(1);
+        let hallway_line = lines
+            .next()
+            .ok_or_else(|| String::from("Missing hallway"))?;
+        let hallway = hallway_line
+            .chars()
+            .skip(1)
+            .take(HALLWAY_LEN)
+            .map(Amphipod::parse)
+            .collect::<Result<Vec<Cell>, Self::Err>>()?
+            .try_into()
+            .map_err(|_| format!("Invalid hallway: {}", hallway_line))?;
+        let mut rooms: [Vec<Cell>; 4] = Default::default();
+        for line in lines.filter(|l| l.chars().any(|c| c != '#' && !c.is_whitespace())) {
+            let row = line
+                .chars()
+                .filter(|c| *c != '#' && !c.is_whitespace())
+                .map(Amphipod::parse)
+                .collect::<Result<Vec<Cell>, Self::Err>>()?;
+            if row.len() != rooms.len() {
+                return Err(format!("Invalid room line: {}", line));
+            }
+            for (room, cell) in rooms.iter_mut().zip(row) {
+                room.push(cell);
+            }
+        }
+        if rooms[0].is_empty() {
+            return Err(String::from("Missing rooms"));
+        }
+        Ok(Self { hallway, rooms })
+    }
+}
+
+impl Burrow {
+    fn is_organized(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(i, room)| {
+            room.iter()
+                .all(|cell| cell.is_some_and(|amphipod| amphipod.room() == i))
+        })
+    }
+
+    /// A room can be entered when it only holds amphipods of its own type
+    fn accepts(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .flatten()
+            .all(|amphipod| amphipod.room() == room)
+    }
+
+    /// Whether the hallway between `from` (excluded) and `to` (included) is empty
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    fn moves(&self) -> Vec<(Self, usize)> {
+        let mut moves = vec![];
+        // Hallway to destination room
+        for (position, amphipod) in self
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| cell.map(|a| (i, a)))
+        {
+            let room = amphipod.room();
+            let entrance = ENTRANCES[room];
+            if !self.accepts(room) || !self.hallway_clear(position, entrance) {
+                continue;
+            }
+            if let Some(depth) = self.rooms[room].iter().rposition(Option::is_none) {
+                let mut burrow = self.clone();
+                burrow.hallway[position] = None;
+                burrow.rooms[room][depth] = Some(amphipod);
+                let steps = position.abs_diff(entrance) + depth + 1;
+                moves.push((burrow, steps * amphipod.energy()));
+            }
+        }
+        // Top of an unsettled room to the hallway
+        for (room, cells) in self.rooms.iter().enumerate() {
+            if self.accepts(room) {
+                continue;
+            }
+            let Some((depth, amphipod)) = cells
+                .iter()
+                .enumerate()
+                .find_map(|(depth, cell)| cell.map(|a| (depth, a)))
+            else {
+                continue;
+            };
+            let entrance = ENTRANCES[room];
+            for position in (0..HALLWAY_LEN).filter(|p| !ENTRANCES.contains(p)) {
+                if !self.hallway_clear(entrance, position) || self.hallway[position].is_some() {
+                    continue;
+                }
+                let mut burrow = self.clone();
+                burrow.rooms[room][depth] = None;
+                burrow.hallway[position] = Some(amphipod);
+                let steps = position.abs_diff(entrance) + depth + 1;
+                moves.push((burrow, steps * amphipod.energy()));
+            }
+        }
+        moves
+    }
+
+    /// Dijkstra over burrow states, returns the least energy required to organize the amphipods
+    fn organize(&self) -> Option<usize> {
+        let mut costs = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        costs.insert(self.clone(), 0);
+        queue.push(Reverse((0, self.clone())));
+        while let Some(Reverse((cost, burrow))) = queue.pop() {
+            if burrow.is_organized() {
+                return Some(cost);
+            }
+            if costs.get(&burrow).is_some_and(|c| *c < cost) {
+                continue;
+            }
+            for (next, energy) in burrow.moves() {
+                let next_cost = cost + energy;
+                if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                    costs.insert(next.clone(), next_cost);
+                    queue.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Inserts the folded part of the diagram after its first room line
+fn unfold(diagram: &str) -> String {
+    let mut lines: Vec<&str> = diagram.lines().collect();
+    lines.splice(3..3, UNFOLDED_ROWS);
+    lines.join("\n")
+}
+
+fn main() {
+    let input = aoc_common::input!(FILE_PATH).unwrap();
+    let burrow = Burrow::from_str(&input).unwrap();
+    println!("Part 1: {}", burrow.organize().unwrap());
+    let burrow = Burrow::from_str(&unfold(&input)).unwrap();
+    println!("Part 2: {}", burrow.organize().unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_folded() {
+        let burrow = Burrow::from_str(EXAMPLE).unwrap();
+        assert_eq!(
+            burrow.rooms[0],
+            vec![Some(Amphipod::Bronze), Some(Amphipod::Amber)]
+        );
+        assert_eq!(burrow.organize(), Some(12521));
+    }
+
+    #[test]
+    fn test_unfolded() {
+        let burrow = Burrow::from_str(&unfold(EXAMPLE)).unwrap();
+        assert_eq!(burrow.rooms[0].len(), 4);
+        assert_eq!(burrow.organize(), Some(44169));
+    }
+}
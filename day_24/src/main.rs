@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+const FILE_PATH: &str = "input.txt";
+const MODEL_NUMBER_LEN: usize = 14;
+/// Every MONAD block is the same 18 instructions, only three operands differ
+const BLOCK_LEN: usize = 18;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Self::W),
+            "x" => Ok(Self::X),
+            "y" => Ok(Self::Y),
+            "z" => Ok(Self::Z),
+            _ => Err(format!("Invalid register {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operand {
+    Register(Register),
+    Value(i64),
+}
+
+impl FromStr for Operand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::Value).or_else(|_| {
+            Register::from_str(s)
+                .map(Self::Register)
+                .map_err(|_| format!("Invalid operand {}", s))
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let name = parts.next().ok_or_else(|| String::from("Empty line"))?;
+        let register = Register::from_str(
+            parts
+                .next()
+                .ok_or_else(|| format!("Missing register in {}", s))?,
+        )?;
+        if name == "inp" {
+            return Ok(Self::Inp(register));
+        }
+        let operand = Operand::from_str(
+            parts
+                .next()
+                .ok_or_else(|| format!("Missing operand in {}", s))?,
+        )?;
+        match name {
+            "add" => Ok(Self::Add(register, operand)),
+            "mul" => Ok(Self::Mul(register, operand)),
+            "div" => Ok(Self::Div(register, operand)),
+            "mod" => Ok(Self::Mod(register, operand)),
+            "eql" => Ok(Self::Eql(register, operand)),
+            _ => Err(format!("Invalid instruction {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    const fn get(&self, register: Register) -> i64 {
+        self.registers[register as usize]
+    }
+
+    const fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.get(r),
+            Operand::Value(v) => v,
+        }
+    }
+
+    fn execute(
+        &mut self,
+        instruction: Instruction,
+        input: &mut impl Iterator<Item = i64>,
+    ) -> Result<(), String> {
+        let (register, value) = match instruction {
+            Instruction::Inp(r) => (r, input.next().ok_or("Missing input")?),
+            Instruction::Add(r, o) => (r, self.get(r) + self.value(o)),
+            Instruction::Mul(r, o) => (r, self.get(r) * self.value(o)),
+            Instruction::Div(r, o) => {
+                let divisor = self.value(o);
+                if divisor == 0 {
+                    return Err(format!("Division by zero in {:?}", instruction));
+                }
+                (r, self.get(r) / divisor)
+            }
+            Instruction::Mod(r, o) => {
+                let (a, b) = (self.get(r), self.value(o));
+                if a < 0 || b <= 0 {
+                    return Err(format!("Invalid modulo {} % {} in {:?}", a, b, instruction));
+                }
+                (r, a % b)
+            }
+            Instruction::Eql(r, o) => (r, i64::from(self.get(r) == self.value(o))),
+        };
+        self.registers[register as usize] = value;
+        Ok(())
+    }
+
+    fn run(
+        &mut self,
+        program: &[Instruction],
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<(), String> {
+        let mut input = input.into_iter();
+        program
+            .iter()
+            .try_for_each(|instruction| self.execute(*instruction, &mut input))
+    }
+}
+
+fn parse_program(s: &str) -> Result<Vec<Instruction>, String> {
+    s.lines().map(Instruction::from_str).collect()
+}
+
+/// The varying operands of a MONAD block. The block reads a digit `w` and, with `z` used as a
+/// base 26 stack, computes `if z % 26 + check != w { push(w + offset) }` after popping when
+/// `divisor` is 26
+#[derive(Debug, Copy, Clone)]
+struct Block {
+    divisor: i64,
+    check: i64,
+    offset: i64,
+}
+
+impl Block {
+    fn extract(instructions: &[Instruction]) -> Result<Self, String> {
+        let operand = |index: usize| match instructions.get(index) {
+            Some(
+                Instruction::Div(_, Operand::Value(v)) | Instruction::Add(_, Operand::Value(v)),
+            ) => Ok(*v),
+            i => Err(format!("Unexpected MONAD instruction {:?}", i)),
+        };
+        Ok(Self {
+            divisor: operand(4)?,
+            check: operand(5)?,
+            offset: operand(15)?,
+        })
+    }
+}
+
+fn extract_blocks(program: &[Instruction]) -> Result<Vec<Block>, String> {
+    if program.len() != MODEL_NUMBER_LEN * BLOCK_LEN {
+        return Err(format!("Unexpected MONAD length {}", program.len()));
+    }
+    program.chunks(BLOCK_LEN).map(Block::extract).collect()
+}
+
+/// Pairs every pushing block with the block popping it: the popped digit must equal the pushed
+/// digit plus the push offset and the pop check, each pair is then chosen independently
+fn model_number(blocks: &[Block], largest: bool) -> Result<[i64; MODEL_NUMBER_LEN], String> {
+    let mut digits = [0; MODEL_NUMBER_LEN];
+    let mut stack = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        match block.divisor {
+            1 => stack.push((i, block.offset)),
+            26 => {
+                let (j, offset) = stack
+                    .pop()
+                    .ok_or_else(|| String::from("Unbalanced MONAD blocks"))?;
+                // digits[i] = digits[j] + delta
+                let delta = offset + block.check;
+                let pushed = if largest {
+                    9.min(9 - delta)
+                } else {
+                    1.max(1 - delta)
+                };
+                if !(1..=9).contains(&pushed) || !(1..=9).contains(&(pushed + delta)) {
+                    return Err(format!("No digits satisfy blocks {} and {}", j, i));
+                }
+                digits[j] = pushed;
+                digits[i] = pushed + delta;
+            }
+            d => return Err(format!("Unexpected MONAD divisor {}", d)),
+        }
+    }
+    if stack.is_empty() {
+        Ok(digits)
+    } else {
+        Err(String::from("Unbalanced MONAD blocks"))
+    }
+}
+
+/// Runs the program on the model number, which is valid if `z` ends up being 0
+fn is_valid(program: &[Instruction], digits: &[i64]) -> Result<bool, String> {
+    let mut alu = Alu::default();
+    alu.run(program, digits.iter().copied())?;
+    Ok(alu.get(Register::Z) == 0)
+}
+
+fn solve(program: &[Instruction], largest: bool) -> String {
+    let digits = extract_blocks(program)
+        .and_then(|blocks| model_number(&blocks, largest))
+        .unwrap();
+    assert!(
+        is_valid(program, &digits).unwrap(),
+        "MONAD rejected {:?}",
+        digits
+    );
+    digits.iter().map(ToString::to_string).collect()
+}
+
+fn main() {
+    let program = parse_program(&aoc_common::input!(FILE_PATH).unwrap()).unwrap();
+    println!("Part 1: {}", solve(&program, true));
+    println!("Part 2: {}", solve(&program, false));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: &[i64]) -> [i64; 4] {
+        let mut alu = Alu::default();
+        alu.run(&parse_program(program).unwrap(), input.iter().copied())
+            .unwrap();
+        alu.registers
+    }
+
+    #[test]
+    fn test_negate() {
+        assert_eq!(run("inp x\nmul x -1", &[7])[1], -7);
+    }
+
+    #[test]
+    fn test_three_times() {
+        let program = include_str!("../test.txt");
+        assert_eq!(run(program, &[2, 6])[3], 1);
+        assert_eq!(run(program, &[2, 7])[3], 0);
+    }
+
+    #[test]
+    fn test_binary() {
+        let program = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\n\
+                       mod x 2\ndiv w 2\nmod w 2";
+        assert_eq!(run(program, &[11]), [1, 0, 1, 1]);
+        assert_eq!(run(program, &[4]), [0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut alu = Alu::default();
+        let program = parse_program("inp x\ndiv y x").unwrap();
+        assert!(alu.run(&program, [0]).is_err());
+        assert!(alu.run(&program, []).is_err());
+        let program = parse_program("add x -1\nmod x 2").unwrap();
+        assert!(Alu::default().run(&program, []).is_err());
+        assert!(Instruction::from_str("sub x 2").is_err());
+        assert!(Instruction::from_str("add a 2").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use aoc_common::simulation::Simulation;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+const FILE_PATH: &str = "input.txt";
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Self::Empty),
+            '>' => Ok(Self::East),
+            'v' => Ok(Self::South),
+            _ => Err(format!("Invalid cell {}", c)),
+        }
+    }
+}
+
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        }
+    }
+}
+
+/// The sea floor wraps around: a sea cucumber leaving an edge reappears on the opposite one
+#[derive(Debug)]
+struct SeaFloor {
+    cells: Vec<Vec<Cell>>,
+    /// Whether a sea cucumber moved during the last step
+    moved: bool,
+}
+
+impl FromStr for SeaFloor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = s
+            .lines()
+            .map(|line| line.chars().map(Cell::try_from).collect())
+            .collect::<Result<Vec<Vec<Cell>>, Self::Err>>()?;
+        let width = cells.first().map_or(0, Vec::len);
+        if width == 0 || cells.iter().any(|row| row.len() != width) {
+            return Err(String::from("Expected a non empty rectangular grid"));
+        }
+        Ok(Self { cells, moved: true })
+    }
+}
+
+impl SeaFloor {
+    /// Moves every sea cucumber of `herd` that faces an empty cell, all at once
+    fn move_herd(&mut self, herd: Cell, [dx, dy]: [usize; 2]) -> bool {
+        let [height, width] = [self.cells.len(), self.cells[0].len()];
+        let moving: Vec<[usize; 2]> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| [x, y]))
+            .filter(|[x, y]| {
+                self.cells[*y][*x] == herd
+                    && self.cells[(y + dy) % height][(x + dx) % width] == Cell::Empty
+            })
+            .collect();
+        for [x, y] in &moving {
+            self.cells[*y][*x] = Cell::Empty;
+            self.cells[(y + dy) % height][(x + dx) % width] = herd;
+        }
+        !moving.is_empty()
+    }
+
+    const fn is_stuck(&self) -> bool {
+        !self.moved
+    }
+}
+
+impl Simulation for SeaFloor {
+    type State = Vec<Vec<Cell>>;
+
+    fn step(&mut self) {
+        let east = self.move_herd(Cell::East, [1, 0]);
+        let south = self.move_herd(Cell::South, [0, 1]);
+        self.moved = east || south;
+    }
+
+    fn state(&self) -> Self::State {
+        self.cells.clone()
+    }
+}
+
+impl Display for SeaFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let buff: Vec<String> = self
+            .cells
+            .iter()
+            .map(|row| row.iter().copied().map(char::from).collect())
+            .collect();
+        write!(f, "{}", buff.join("\n"))
+    }
+}
+
+fn main() {
+    let mut sea_floor = SeaFloor::from_str(&aoc_common::input!(FILE_PATH).unwrap()).unwrap();
+    let steps = sea_floor.run_until(SeaFloor::is_stuck);
+    println!("Part 1: {}", steps);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        let mut sea_floor = SeaFloor::from_str("...>>>>>...").unwrap();
+        sea_floor.step();
+        assert_eq!(sea_floor.to_string(), "...>>>>.>..");
+        sea_floor.step();
+        assert_eq!(sea_floor.to_string(), "...>>>.>.>.");
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let mut sea_floor =
+            SeaFloor::from_str("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..")
+                .unwrap();
+        sea_floor.run(4);
+        assert_eq!(
+            sea_floor.to_string(),
+            ">......\n..v....\n..>.v..\n.>.v...\n...>...\n.......\nv......"
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        let mut sea_floor = SeaFloor::from_str(include_str!("../test.txt")).unwrap();
+        sea_floor.run(10);
+        assert_eq!(
+            sea_floor.to_string(),
+            "..>..>>vv.\nv.....>>.v\n..v.v>>>v>\nv>.>v.>>>.\n..v>v.vv.v\n.v.>>>.v..\nv.v..>v>..\n..v...>v.>\n.vv..v>vv."
+        );
+        assert_eq!(sea_floor.run_until(SeaFloor::is_stuck), 48);
+    }
+
+    #[test]
+    fn test_rendering() {
+        let mut sea_floor = SeaFloor::from_str(include_str!("../test.txt")).unwrap();
+        sea_floor.run_until(SeaFloor::is_stuck);
+        insta::assert_snapshot!(sea_floor.to_string());
+    }
+}